@@ -0,0 +1,219 @@
+use bevy::prelude::*;
+use wordle::util::upper;
+use crate::{all_correct, SysLabel, TileType};
+use crate::anim::FlipLens;
+use crate::banner::win_message_key;
+use crate::components::{GameSettings, Tile, TileMap, UnknownAnswer, Word};
+use crate::events::EndFlipAnim;
+use crate::locale::Localization;
+use crate::toast::ShowToast;
+use crate::tween::{Tween, tween_system};
+
+/// The ids of the elements that screen readers watch on the web.
+#[cfg(target_family = "wasm")]
+const LIVE_REGION_IDS: [&str; 2] = ["wordle-announcements-0", "wordle-announcements-1"];
+
+/// Describes the game in words, for players using a screen reader.
+///
+/// On the web, [`Announce`] events are put in ARIA live regions, which screen readers read out. Natively they are
+/// printed to the terminal, as the window isn't connected to the platform's accessibility API.
+pub struct AccessibilityPlugin;
+
+impl Plugin for AccessibilityPlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.add_event::<Announce>()
+			.init_resource::<Spoken>()
+			.add_system_set(SystemSet::new()
+				.label(SysLabel::Anim)
+				.after(SysLabel::Input)
+				.with_system(announce_tiles.after(tween_system::<FlipLens>))
+				.with_system(announce_toasts)
+				.with_system(announce_result)
+				.with_system(read_board)
+				.with_system(speak.after(announce_tiles).after(announce_toasts).after(announce_result).after(read_board))
+			)
+		;
+		
+		#[cfg(target_family = "wasm")]
+		app.add_startup_system(setup_live_regions);
+	}
+}
+
+/// An event that sends a message to the screen reader.
+pub struct Announce(pub String);
+
+/// What has been said to the screen reader.
+#[derive(Default)]
+#[derive(Resource)]
+struct Spoken {
+	/// The text of each live region. They take turns, so that a message is read out again when it repeats.
+	live: [String; 2],
+	/// The live region that was written last.
+	live_idx: usize,
+}
+
+/// Describes the state of a tile or key, like "correct".
+pub fn describe_state(tt: TileType, loc: &Localization) -> &str {
+	match tt {
+		TileType::Correct => loc.get("a11y-correct"),
+		TileType::Close => loc.get("a11y-close"),
+		TileType::Wrong => loc.get("a11y-wrong"),
+		TileType::Default => loc.get("a11y-empty"),
+	}
+}
+
+/// Describes a tile, like "R, correct, position 2".
+fn describe_tile(tile: &Tile, loc: &Localization) -> String {
+	let position = tile.x + 1;
+	match tile.c {
+		Some(c) => loc.format("a11y-tile", &[("letter", &c), ("state", &describe_state(tile.tt, loc)), ("position", &position)]),
+		None => loc.format("a11y-empty-tile", &[("position", &position)]),
+	}
+}
+
+/// Describes each tile as it is flipped over.
+fn announce_tiles(
+	tiles: Query<(&Tile, &Tween<FlipLens>)>,
+	loc: Res<Localization>,
+	mut announce_w: EventWriter<Announce>,
+) {
+	let mut tiles: Vec<_> = tiles.iter()
+		.filter(|(_, tween)| tween.crossed(0.5))
+		.map(|(tile, _)| tile)
+		.collect();
+	// Tiles can cross in the same frame, like with reduced motion.
+	tiles.sort_by_key(|tile| tile.x);
+	
+	for tile in tiles {
+		announce_w.send(Announce(describe_tile(tile, &loc)));
+	}
+}
+
+/// Reads out toasts, like why a guess was rejected.
+fn announce_toasts(
+	mut toast_r: EventReader<ShowToast>,
+	mut announce_w: EventWriter<Announce>,
+) {
+	for toast in toast_r.iter() {
+		announce_w.send(Announce(toast.0.clone()));
+	}
+}
+
+/// Reads out the result once the last row of the game is revealed.
+fn announce_result(
+	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+	settings: Res<GameSettings>,
+	word: Res<Word>,
+	unknown_answer: Option<Res<UnknownAnswer>>,
+	loc: Res<Localization>,
+	mut announce_w: EventWriter<Announce>,
+) {
+	// Whoever picked the answer says how the game ended, like the bot.
+	if unknown_answer.is_some() { return; }
+	
+	for end_flip_anim in end_flip_anim_r.iter() {
+		let end_flip_anim: &EndFlipAnim = end_flip_anim;
+		
+		let message = if all_correct(&end_flip_anim.correctness) {
+			loc.get(win_message_key(end_flip_anim.row, settings.guesses)).to_owned()
+		} else if end_flip_anim.row + 1 == settings.guesses {
+			let answer: String = word.chars().map(upper).collect();
+			loc.format("a11y-lost", &[("word", &answer)])
+		} else {
+			continue;
+		};
+		
+		announce_w.send(Announce(message));
+	}
+}
+
+/// Reads out every guess so far when F2 is pressed.
+/// Browsers use most Ctrl shortcuts, so a function key is used.
+fn read_board(
+	keys: Res<Input<KeyCode>>,
+	tiles_q: Query<&Tile>,
+	tile_map: Res<TileMap>,
+	loc: Res<Localization>,
+	mut announce_w: EventWriter<Announce>,
+) {
+	if !keys.just_pressed(KeyCode::F2) { return; }
+	
+	let mut rows = 0;
+	for (idx, row) in tile_map.iter().enumerate() {
+		let tiles: Vec<&Tile> = row.iter().map(|e| tiles_q.get(*e).unwrap()).collect();
+		// Only rows that have been guessed.
+		if tiles.iter().any(|tile| tile.tt == TileType::Default) { continue; }
+		
+		let description = tiles.iter()
+			.map(|tile| describe_tile(tile, &loc))
+			.collect::<Vec<_>>()
+			.join("; ");
+		announce_w.send(Announce(loc.format("a11y-row", &[("row", &(idx + 1)), ("tiles", &description)])));
+		rows += 1;
+	}
+	
+	if rows == 0 {
+		announce_w.send(Announce(loc.get("a11y-no-guesses").to_owned()));
+	}
+}
+
+/// Hands announcements to the screen reader, through the live regions, or prints them natively.
+fn speak(
+	mut announce_r: EventReader<Announce>,
+	mut spoken: ResMut<Spoken>,
+) {
+	let announcements: Vec<&str> = announce_r.iter().map(|announce| announce.0.as_str()).collect();
+	if announcements.is_empty() { return; }
+	
+	// Screen readers only read a live region when its text changes, so the same message twice in a row would be
+	// skipped. Writing to the other region, and clearing this one, makes every message a change.
+	let idx = 1 - spoken.live_idx;
+	spoken.live_idx = idx;
+	spoken.live[1 - idx].clear();
+	spoken.live[idx] = announcements.join(". ");
+	
+	#[cfg(target_family = "wasm")]
+	set_live_regions(&spoken.live);
+	#[cfg(not(target_family = "wasm"))]
+	println!("{}", spoken.live[idx]);
+}
+
+/// Adds the live regions to the page, which are hidden but still read by screen readers.
+#[cfg(target_family = "wasm")]
+fn setup_live_regions() {
+	const STYLE: &str = "position: absolute; width: 1px; height: 1px; overflow: hidden; clip: rect(0 0 0 0);";
+	
+	let document = match web_sys::window().and_then(|window| window.document()) {
+		Some(document) => document,
+		None => return,
+	};
+	let body = match document.body() {
+		Some(body) => body,
+		None => return,
+	};
+	
+	for id in LIVE_REGION_IDS {
+		if let Ok(region) = document.create_element("div") {
+			region.set_id(id);
+			let _ = region.set_attribute("role", "status");
+			let _ = region.set_attribute("aria-live", "polite");
+			let _ = region.set_attribute("style", STYLE);
+			let _ = body.append_child(&region);
+		}
+	}
+}
+
+#[cfg(target_family = "wasm")]
+fn set_live_regions(texts: &[String; 2]) {
+	let document = match web_sys::window().and_then(|window| window.document()) {
+		Some(document) => document,
+		None => return,
+	};
+	
+	for (id, text) in LIVE_REGION_IDS.iter().zip(texts) {
+		if let Some(region) = document.get_element_by_id(id) {
+			region.set_text_content(Some(text));
+		}
+	}
+}
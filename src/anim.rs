@@ -1,13 +1,13 @@
 use std::f32::consts::PI;
 use std::time::Duration;
 use bevy::prelude::*;
-use rand::{Rng, thread_rng};
-use statrs::distribution::{ContinuousCDF, Normal};
-use crate::{App, Confetti, ConfettiSpawner, get_tile_pos, Guess, InvalidGuess, Pause, PauseLock, SysLabel, TileType, TypedLetter};
-use crate::components::{Tile, TileAssets, TileMap};
-use crate::events::EndFlipAnim;
+use wordle::util::{lower, upper};
+use crate::{all_correct, App, get_tile_pos, Guess, InvalidGuess, Pause, PauseLock, spawn_tile, SysLabel, TILE_TOTAL, TileType, TypedLetter};
+use crate::components::{AnimSettings, Answer, GameSettings, Tile, TileAssets, TileMap, Title, UnknownAnswer, Word};
+use crate::events::{EndFlipAnim, InputAction};
 use crate::keyboard::Key;
-use crate::util::GetKeyCode;
+use crate::theme::Theme;
+use crate::tween::{Ease, Lens, stagger, Tween, TweenAppExt, TweenDone, tween_system};
 
 const JUMP_ANIM_TIME: Duration = Duration::from_millis(100);
 const FLIP_ANIM_TIME: Duration = Duration::from_millis(300);
@@ -15,36 +15,34 @@ const SHAKE_ANIM_TIME: Duration = Duration::from_millis(500);
 const WAVE_ANIM_TIME: Duration = Duration::from_millis(200);
 const WAVE_AMPL: f32 = 30.0;
 
-const CONFETTI_COUNT: u32 = 200;
-
 pub struct AnimPlugin;
 
 impl Plugin for AnimPlugin {
 	fn build(&self, app: &mut App) {
 		app
-			.add_startup_system(setup_confetti_spawners)
+			.add_tween::<ShakeLens>()
+			.add_tween::<JumpLens>()
+			.add_tween::<FlipLens>()
+			.add_tween::<WaveLens>()
+			.add_tween::<SquashLens>()
+			.add_tween::<ScaleLens>()
 		
 			.add_system_set(SystemSet::new()
 				.label(SysLabel::Anim)
-				.after(SysLabel::Logic)
+				.after(SysLabel::Input)
 				
-				.with_system(color_keyboard)
-				.with_system(set_keyboard_color)
 				.with_system(keyboard_jump)
-				.with_system(spawn_confetti)
-				.with_system(confetti_move)
-				.with_system(confetti_gravity)
-				
 				
 				.with_system(start_shake)
 				.with_system(start_jump)
 				.with_system(start_flip)
 				.with_system(start_wave)
+				.with_system(start_loss)
 				
-				.with_system(shake_anim)
-				.with_system(jump_anim)
-				.with_system(flip_anim)
-				.with_system(wave_anim)
+				.with_system(flip_color.after(tween_system::<FlipLens>))
+				.with_system(flip_key_color.after(tween_system::<FlipLens>))
+				.with_system(reveal_color)
+				.with_system(end_flip_chain.after(tween_system::<FlipLens>))
 			)
 		;
 	}
@@ -54,12 +52,16 @@ fn start_shake(
 	mut commands: Commands,
 	mut inv_guess_r: EventReader<InvalidGuess>,
 	tile_map: Res<TileMap>,
+	settings: Res<GameSettings>,
+	anim_settings: Res<AnimSettings>,
 ) {
 	for inv_guess in inv_guess_r.iter() {
 		let inv_guess: &InvalidGuess = inv_guess;
+		if anim_settings.reduced_motion { continue; }
 		
-		for entity in tile_map[inv_guess.row].iter() {
-			commands.entity(*entity).insert(ShakeAnim::new());
+		for (x, entity) in tile_map[inv_guess.row].iter().enumerate() {
+			let base = get_tile_pos(x, inv_guess.row, &settings);
+			commands.entity(*entity).insert(Tween::new(ShakeLens { base }, SHAKE_ANIM_TIME));
 		}
 	}
 }
@@ -68,25 +70,38 @@ fn start_jump(
 	mut commands: Commands,
 	mut typed_letter_r: EventReader<TypedLetter>,
 	tile_map: Res<TileMap>,
+	anim_settings: Res<AnimSettings>,
 ) {
 	for typed_letter in typed_letter_r.iter() {
 		let typed_letter: &TypedLetter = typed_letter;
 		
-		if typed_letter.valid {
-			commands.entity(tile_map[typed_letter.y][typed_letter.x]).insert(JumpAnim::new());
+		if typed_letter.valid && !anim_settings.reduced_motion {
+			commands.entity(tile_map[typed_letter.y][typed_letter.x]).insert(Tween::new(JumpLens, JUMP_ANIM_TIME));
 		}
 	}
 }
 
+/// Flips the tiles of the guessed row one after another.
+/// The game is paused until the last tile is done.
+/// With reduced motion, the tiles change color all at once instead.
 fn start_flip(
 	mut commands: Commands,
 	mut guess_r: EventReader<Guess>,
 	tile_map: Res<TileMap>,
 	pause: Res<Pause>,
+	anim_settings: Res<AnimSettings>,
 ) {
+	let flip_time = if anim_settings.reduced_motion { Duration::ZERO } else { FLIP_ANIM_TIME };
+	
 	for guess in guess_r.iter() {
 		let guess: &Guess = guess;
-		commands.entity(tile_map[guess.row][0]).insert(FlipAnim::new(pause.lock(), guess.into()));
+		let row = &tile_map[guess.row];
+		
+		stagger(&mut commands, row.iter().copied(), flip_time, |_| Tween::new(FlipLens, flip_time));
+		commands.entity(row[row.len() - 1]).insert(FlipChain {
+			_pause_lock: pause.lock(),
+			end_event: guess.into(),
+		});
 	}
 }
 
@@ -94,168 +109,183 @@ fn start_wave(
 	mut commands: Commands,
 	mut end_flip_anim_r: EventReader<EndFlipAnim>,
 	tile_map: Res<TileMap>,
+	settings: Res<GameSettings>,
+	anim_settings: Res<AnimSettings>,
 ) {
 	for end_flip_anim in end_flip_anim_r.iter() {
 		let end_flip_anim: &EndFlipAnim = end_flip_anim;
 		
-		if end_flip_anim.correctness == [TileType::Correct; 5] {
-			commands.entity(tile_map[end_flip_anim.row][0]).insert(WaveAnim::new());
+		if all_correct(&end_flip_anim.correctness) && !anim_settings.reduced_motion {
+			let row = end_flip_anim.row;
+			stagger(&mut commands, tile_map[row].iter().copied(), WAVE_ANIM_TIME, |x| {
+				let base = get_tile_pos(x, row, &settings);
+				Tween::new(WaveLens { base }, WAVE_ANIM_TIME).with_ease(Ease::SineInOut)
+			});
 		}
 	}
 }
 
-
-fn shake_anim(
+/// Once the last row is revealed without a win, shakes the row, then spells out the answer where the title was.
+/// An [`UnknownAnswer`] is not spelled out.
+fn start_loss(
 	mut commands: Commands,
-	mut tiles: Query<(Entity, &Tile, &mut Transform, &mut ShakeAnim)>,
-	time: Res<Time>,
+	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+	title_q: Query<Entity, With<Title>>,
+	tile_map: Res<TileMap>,
+	word: Res<Word>,
+	unknown_answer: Option<Res<UnknownAnswer>>,
+	settings: Res<GameSettings>,
+	anim_settings: Res<AnimSettings>,
+	tile_assets: Res<TileAssets>,
+	asset_server: Res<AssetServer>,
+	theme: Res<Theme>,
 ) {
-	for (entity, tile, transform, anim) in tiles.iter_mut() {
-		let entity: Entity = entity;
-		let tile: &Tile = tile;
-		let mut transform: Mut<Transform> = transform;
-		let mut anim: Mut<ShakeAnim> = anim;
+	for end_flip_anim in end_flip_anim_r.iter() {
+		let end_flip_anim: &EndFlipAnim = end_flip_anim;
+		let row = end_flip_anim.row;
+		if all_correct(&end_flip_anim.correctness) || row + 1 != settings.guesses { continue; }
 		
-		let mut pos = get_tile_pos(tile.x as i32, tile.y as i32);
+		let (shake_time, reveal_time) = if anim_settings.reduced_motion {
+			(Duration::ZERO, Duration::ZERO)
+		} else {
+			(SHAKE_ANIM_TIME, FLIP_ANIM_TIME)
+		};
 		
-		if anim.tick(time.delta()) { // Finished
-			transform.translation = pos;
-			commands.entity(entity).remove::<ShakeAnim>();
-			continue;
+		if !anim_settings.reduced_motion {
+			for (x, entity) in tile_map[row].iter().enumerate() {
+				let base = get_tile_pos(x, row, &settings);
+				commands.entity(*entity).insert(Tween::new(ShakeLens { base }, shake_time));
+			}
 		}
 		
-		// Shake
-		let shake_offset = (anim.scale() * PI).sin() * 8.0 * (anim.scale() * PI * 8.0).sin();
-		pos.x += shake_offset;
-		transform.translation = pos;
+		if unknown_answer.is_some() { continue; }
+		
+		// Make room for the answer.
+		for title in title_q.iter() {
+			let squash = SquashLens { from: 1.0, to: 0.0 };
+			commands.entity(title).insert(Tween::new(squash, shake_time).with_ease(Ease::QuadIn));
+		}
+		
+		let font = asset_server.load("fonts/Swansea.ttf");
+		for (x, c) in word.chars().enumerate() {
+			// One row above the board.
+			let mut translation = get_tile_pos(x, 0, &settings);
+			translation.y += TILE_TOTAL;
+			
+			let entity = spawn_tile(&mut commands, tile_assets.default.clone(), font.clone(), x, row, &settings, *theme);
+			let squash = SquashLens { from: 0.0, to: 1.0 };
+			commands.entity(entity)
+				.insert(Tile { tt: TileType::Correct, c: Some(upper(c)), x: x as u32, y: settings.guesses as u32 })
+				.insert(Answer)
+				// Start edge on, so that the answer is hidden until its tile flips in.
+				.insert(Transform {
+					translation,
+					scale: Vec3::new(1.0, 0.0, 1.0),
+					..Default::default()
+				})
+				.insert(Tween::new(squash, reveal_time / 2)
+					.with_delay(shake_time + reveal_time * x as u32)
+					.with_ease(Ease::QuadOut)
+				);
+		}
 	}
 }
 
-fn jump_anim(
-	mut commands: Commands,
-	mut tiles: Query<(Entity, &mut Transform, &mut JumpAnim)>,
-	time: Res<Time>,
+/// Colors the tiles once they are flipped half way.
+fn flip_color(
+	mut tiles: Query<(&Tile, &Children, &mut Handle<Image>, &mut Sprite, &Tween<FlipLens>)>,
+	mut text_q: Query<&mut Text>,
+	tile_assets: Res<TileAssets>,
+	theme: Res<Theme>,
 ) {
-	for (entity, transform, anim) in tiles.iter_mut() {
-		let entity: Entity = entity;
-		let mut transform: Mut<Transform> = transform;
-		let mut anim: Mut<JumpAnim> = anim;
+	for (tile, children, mut texture, mut sprite, tween) in tiles.iter_mut() {
+		let tween: &Tween<FlipLens> = tween;
 		
-		if anim.tick(time.delta()) { // Finished
-			transform.scale = Vec3::ONE;
-			commands.entity(entity).remove::<JumpAnim>();
-			continue;
+		if tween.crossed(0.5) {
+			color_tile(tile, children, &mut texture, &mut sprite, &mut text_q, &tile_assets, &theme);
 		}
-		
-		// Calculate new size
-		let size = 1.0 + 0.1 * (PI * anim.scale()).sin();
-		transform.scale = Vec3::splat(size);
 	}
 }
 
-fn flip_anim(
-	mut commands: Commands,
-	mut tiles: Query<(Entity, &mut Transform, &Tile, &mut Handle<Image>, &mut Sprite, &mut FlipAnim)>,
-	time: Res<Time>,
+/// Colors revealed tiles, which flip in already facing the player.
+fn reveal_color(
+	mut tiles: Query<(&Tile, &Children, &mut Handle<Image>, &mut Sprite), Added<Tween<SquashLens>>>,
+	mut text_q: Query<&mut Text>,
 	tile_assets: Res<TileAssets>,
-	tile_map: Res<TileMap>,
-	mut end_flip_anim_w: EventWriter<EndFlipAnim>,
+	theme: Res<Theme>,
 ) {
-	for (entity, transform, tile, texture, sprite, anim) in tiles.iter_mut() {
-		let entity: Entity = entity;
-		let mut transform: Mut<Transform> = transform;
-		let tile: &Tile = tile;
-		let mut texture: Mut<Handle<Image>> = texture;
-		let mut sprite: Mut<Sprite> = sprite;
-		let mut anim: Mut<FlipAnim> = anim;
-		
-		if anim.tick(time.delta()) { // Finished
-			transform.scale = Vec3::ONE;
-			commands.entity(entity).remove::<FlipAnim>();
-			// Give the next tile the flip anim
-			let x = tile.x as usize + 1;
-			let y = tile.y as usize;
-			if x < tile_map[0].len() {
-				commands.entity(tile_map[y][x]).insert(FlipAnim::new(anim.pause_lock.clone(), anim.end_event.clone()));
-			} else {
-				end_flip_anim_w.send(anim.end_event.clone());
-			}
-			
-			continue;
-		}
-		
-		if anim.should_change() {
-			*texture = tile_assets.colored.clone();
-			sprite.color = tile.tt.color();
-		}
-		
-		let scale = (anim.scale()-0.5).abs() * 2.0;
-		transform.scale.y = scale;
+	for (tile, children, mut texture, mut sprite) in tiles.iter_mut() {
+		color_tile(tile, children, &mut texture, &mut sprite, &mut text_q, &tile_assets, &theme);
 	}
 }
 
-fn wave_anim(
-	mut commands: Commands,
-	mut tiles: Query<(Entity, &mut Transform, &Tile, &mut WaveAnim)>,
-	time: Res<Time>,
-	tile_map: Res<TileMap>,
+/// Sets the texture and colors of a tile for its [`TileType`].
+fn color_tile(
+	tile: &Tile,
+	children: &Children,
+	texture: &mut Handle<Image>,
+	sprite: &mut Sprite,
+	text_q: &mut Query<&mut Text>,
+	tile_assets: &TileAssets,
+	theme: &Theme,
 ) {
-	for (entity, transform, tile, anim) in tiles.iter_mut() {
-		let entity: Entity = entity;
-		let mut transform: Mut<Transform> = transform;
-		let tile: &Tile = tile;
-		let mut anim: Mut<WaveAnim> = anim;
-		
-		if anim.tick(time.delta()) { // Finished
-			// TODO: change fn signature of `get_tile_pos` to use usize.
-			transform.translation = get_tile_pos(tile.x as i32, tile.y as i32);
-			commands.entity(entity).remove::<WaveAnim>();
-			// Give the next tile the flip anim
-			let x = tile.x as usize + 1;
-			let y = tile.y as usize;
-			if x < tile_map[0].len() {
-				commands.entity(tile_map[y][x]).insert(WaveAnim::new());
-			}
-			
-			continue;
-		}
-		
-		let mut pos = get_tile_pos(tile.x as i32, tile.y as i32);
-		let height = (anim.scale() * PI).sin() * WAVE_AMPL;
-		pos.y += height;
-		
-		transform.translation = pos;
+	*texture = tile_assets.colored.clone();
+	sprite.color = theme.tile_color(tile.tt);
+	
+	// The first child holds the letter
+	if let Ok(mut text) = text_q.get_mut(children[0]) {
+		text.sections[0].style.color = theme.letter_color(tile.tt);
 	}
 }
 
-fn color_keyboard(
-	mut keys_q: Query<(&mut BackgroundColor, &Key)>,
-	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+/// Fires the [`EndFlipAnim`] event once the last tile of a row is flipped.
+fn end_flip_chain(
+	mut commands: Commands,
+	mut tween_done_r: EventReader<TweenDone<FlipLens>>,
+	chain_q: Query<&FlipChain>,
+	mut end_flip_anim_w: EventWriter<EndFlipAnim>,
 ) {
-	for _ in end_flip_anim_r.iter() {
-		for (color, key) in keys_q.iter_mut() {
-			let mut color: Mut<BackgroundColor> = color;
-			let key: &Key = key;
-			
-			color.0 = key.tt.color()
+	for tween_done in tween_done_r.iter() {
+		if let Ok(chain) = chain_q.get(tween_done.entity) {
+			end_flip_anim_w.send(chain.end_event.clone());
+			// Removing the chain drops the pause lock.
+			commands.entity(tween_done.entity).remove::<FlipChain>();
 		}
 	}
 }
 
-fn set_keyboard_color(
-	mut keys_q: Query<&mut Key>,
-	mut guess_r: EventReader<Guess>
+/// Colors the key of a tile's letter once the tile is flipped half way, so that the keyboard reveals each letter
+/// together with its tile. The key jumps when it gets better info.
+fn flip_key_color(
+	mut commands: Commands,
+	tiles: Query<(&Tile, &Tween<FlipLens>)>,
+	mut keys_q: Query<(Entity, &mut Key, &mut BackgroundColor, &Children)>,
+	mut text_q: Query<&mut Text>,
+	anim_settings: Res<AnimSettings>,
+	theme: Res<Theme>,
 ) {
-	for guess in guess_r.iter() {
-		let guess: &Guess = guess;
+	for (tile, tween) in tiles.iter() {
+		let tile: &Tile = tile;
+		let tween: &Tween<FlipLens> = tween;
+		
+		if !tween.crossed(0.5) { continue; }
+		let c = match tile.c {
+			Some(c) => c,
+			None => continue,
+		};
 		
-		for mut key in keys_q.iter_mut() {
-			// If the guess had this key's letter.
-			if let Some(idx) = guess.word.chars().position(|c| c == key.key.to_ascii_lowercase()) {
-				// If the guess has better info, update it.
-				if key.tt > guess.correctness[idx] {
-					key.tt = guess.correctness[idx];
-				}
+		for (entity, mut key, mut color, children) in keys_q.iter_mut() {
+			// Only update the key if the tile has better info.
+			if key.letter() != Some(lower(c)) || key.tt <= tile.tt { continue; }
+			
+			key.tt = tile.tt;
+			color.0 = theme.tile_color(key.tt);
+			if let Ok(mut text) = text_q.get_mut(children[0]) {
+				text.sections[0].style.color = theme.letter_color(key.tt);
+			}
+			
+			if !anim_settings.reduced_motion {
+				commands.entity(entity).insert(Tween::new(JumpLens, JUMP_ANIM_TIME));
 			}
 		}
 	}
@@ -263,241 +293,132 @@ fn set_keyboard_color(
 
 fn keyboard_jump(
 	mut commands: Commands,
-	keys: Res<Input<KeyCode>>,
-	keys_q: Query<(Entity, &Key)>
+	mut input_r: EventReader<InputAction>,
+	keys_q: Query<(Entity, &Key)>,
+	anim_settings: Res<AnimSettings>,
 ) {
-	for key in keys.get_just_pressed() {
-		let key: &KeyCode = key;
+	for input in input_r.iter() {
+		let input: &InputAction = input;
+		if anim_settings.reduced_motion { continue; }
 		
 		for (entity, key_c) in keys_q.iter() {
 			let entity: Entity = entity;
 			let key_c: &Key = key_c;
 			
-			if key_c.key.get_keycode().unwrap() == *key {
-				commands.entity(entity).insert(JumpAnim::new());
+			if *input == key_c.action {
+				commands.entity(entity).insert(Tween::new(JumpLens, JUMP_ANIM_TIME));
 			}
 		}
 	}
 }
 
-fn confetti_move(
-	mut confetti_q: Query<(&mut Transform, &Confetti)>
-) {
-	for (transform, confetti) in confetti_q.iter_mut() {
-		let mut transform: Mut<Transform> = transform;
-		let confetti: &Confetti = confetti;
-		
-		transform.translation += confetti.velocity;
-		transform.rotation = transform.rotation.mul_quat(confetti.rot);
-	}
-}
-
-fn confetti_gravity(
-	mut confetti_q: Query<&mut Confetti>,
-	time: Res<Time>,
-) {
-	for mut confetti in confetti_q.iter_mut() {
-		confetti.velocity.y -= time.delta_seconds() * 5.0;
-	}
+/// Shakes a tile from side to side around its position.
+pub struct ShakeLens {
+	/// The resting position of the tile.
+	pub base: Vec3,
 }
 
-fn spawn_confetti(
-	mut commands: Commands,
-	confetti_spawner_q: Query<(&Transform, &ConfettiSpawner)>,
-	mut end_flip_anim_r: EventReader<EndFlipAnim>,
-) {
-	for end_flip_anim in end_flip_anim_r.iter() {
-		let end_flip_anim: &EndFlipAnim = end_flip_anim;
-		
-		let mut rng = thread_rng();
-		
-		if end_flip_anim.correctness == [TileType::Correct; 5] {
-			for (transform, confetti_spawner) in confetti_spawner_q.iter() {
-				let transform: &Transform = transform;
-				let confetti_spawner: &ConfettiSpawner = confetti_spawner;
-				
-				let v_x = confetti_spawner.dir.x;
-				let v_y = confetti_spawner.dir.y;
-				
-				let normal_x = Normal::new(v_x as f64, (v_x.abs() / 2.0) as f64).unwrap();
-				let normal_y = Normal::new(v_y as f64, (v_y.abs() / 2.0) as f64).unwrap();
-				
-				for _i in 0..CONFETTI_COUNT {
-					let velocity = Vec3::new(
-						normal_x.inverse_cdf(rng.gen_range(0.0..1.0)) as f32,
-						normal_y.inverse_cdf(rng.gen_range(0.0..1.0)) as f32,
-						1.0,
-					);
-					
-					let rot = Quat::from_rotation_z(rng.gen_range(0.0..0.5));
-					
-					commands
-						.spawn(SpriteBundle {
-							sprite: Sprite {
-								color: Color::rgb_u8(rng.gen(), rng.gen(), rng.gen()),
-								custom_size: Some(Vec2::new(10.0, 5.0)),
-								..Default::default()
-							},
-							transform: transform.clone(),
-							..Default::default()
-						})
-						.insert(Confetti {
-							velocity,
-							rot,
-						})
-					;
-				}
-			}
-		}
+impl Lens for ShakeLens {
+	type Target = Transform;
+	
+	fn apply(&self, target: &mut Transform, t: f32) {
+		let shake_offset = (t * PI).sin() * 8.0 * (t * PI * 8.0).sin();
+		target.translation = self.base + Vec3::X * shake_offset;
 	}
-}
-
-// TODO: place these at the edge of the screen
-fn setup_confetti_spawners(
-	mut commands: Commands,
-) {
-	commands
-		.spawn(ConfettiSpawner {
-			dir: Vec3::new(-5.0, 7.0, 0.0),
-		})
-		.insert(Transform::from_translation(Vec3::new(550.0, -200.0, 0.0)));
 	
-	commands
-		.spawn(ConfettiSpawner {
-			dir: Vec3::new(5.0, 7.0, 0.0),
-		})
-		.insert(Transform::from_translation(Vec3::new(-550.0, -200.0, 0.0)));
+	fn finish(&self, target: &mut Transform) {
+		target.translation = self.base;
+	}
 }
 
-/// The animation for shaking the tiles.
-#[derive(Component)]
-pub struct ShakeAnim {
-	/// Elapsed duration.
-	d: Duration,
-}
+/// The Jump animation for adding a letter.
+pub struct JumpLens;
 
-impl ShakeAnim {
-	/// Constructs a new [`ShakeAnim`]
-	pub fn new() -> Self {
-		Self {
-			d: Duration::ZERO,
-		}
-	}
+impl Lens for JumpLens {
+	type Target = Transform;
 	
-	/// Ticks by duration, then returns weather it finished.
-	pub fn tick(&mut self, dur: Duration) -> bool {
-		self.d += dur;
-		self.d > SHAKE_ANIM_TIME
+	fn apply(&self, target: &mut Transform, t: f32) {
+		let size = 1.0 + 0.1 * (PI * t).sin();
+		target.scale = Vec3::splat(size);
 	}
 	
-	/// Returns a float of how completed the animation is.
-	pub fn scale(&self) -> f32 {
-		self.d.as_secs_f32() / SHAKE_ANIM_TIME.as_secs_f32()
+	fn finish(&self, target: &mut Transform) {
+		target.scale = Vec3::ONE;
 	}
 }
 
-/// The Jump animation for adding a letter.
-#[derive(Component)]
-pub struct JumpAnim {
-	/// Elapsed duration.
-	d: Duration,
-}
-
+/// The animation for flipping the tile color.
+/// The color changes when the tile is edge on, half way through.
+pub struct FlipLens;
 
-impl JumpAnim {
-	/// Constructs a new [`JumpAnim`]
-	pub fn new() -> Self {
-		Self {
-			d: Duration::ZERO,
-		}
-	}
+impl Lens for FlipLens {
+	type Target = Transform;
 	
-	/// Ticks by duration, then returns weather it finished.
-	pub fn tick(&mut self, dur: Duration) -> bool {
-		self.d += dur;
-		self.d > JUMP_ANIM_TIME
+	fn apply(&self, target: &mut Transform, t: f32) {
+		target.scale.y = (t - 0.5).abs() * 2.0;
 	}
 	
-	/// Returns a float of how completed the animation is.
-	pub fn scale(&self) -> f32 {
-		self.d.as_secs_f32() / JUMP_ANIM_TIME.as_secs_f32()
+	fn finish(&self, target: &mut Transform) {
+		target.scale = Vec3::ONE;
 	}
 }
 
-/// The animation for flipping the tile color.
+/// Put on the last tile of a flipping row.
 #[derive(Component)]
-pub struct FlipAnim {
-	/// Elapsed duration.
-	d: Duration,
-	/// Weather this should change.
-	should_change: bool,
-	/// Weather this has changed.
-	changed: bool,
+pub struct FlipChain {
 	/// The flip animation should pause the game.
-	pause_lock: PauseLock,
+	_pause_lock: PauseLock,
 	/// The end flip animation event that should be fired once the chain is done.
 	end_event: EndFlipAnim,
 }
 
-impl FlipAnim {
-	/// Constructs a new [`FlipAnim`]
-	pub fn new(pause_lock: PauseLock, end_event: EndFlipAnim) -> Self {
-		Self {
-			d: Duration::ZERO,
-			should_change: false,
-			changed: false,
-			pause_lock,
-			end_event,
-		}
-	}
-	
-	/// Ticks by duration, then returns weather it finished.
-	pub fn tick(&mut self, dur: Duration) -> bool {
-		self.d += dur;
-		if !self.changed && self.d > FLIP_ANIM_TIME / 2 {
-			self.changed = true;
-			self.should_change = true;
-		}
-		
-		self.d > FLIP_ANIM_TIME
-	}
+/// The animation for the wave that the letters do on win.
+pub struct WaveLens {
+	/// The resting position of the tile.
+	pub base: Vec3,
+}
+
+impl Lens for WaveLens {
+	type Target = Transform;
 	
-	/// Returns weather the image should change. This will return exactly true once.
-	pub fn should_change(&mut self) -> bool {
-		let should_change = self.should_change;
-		self.should_change = false;
-		should_change
+	fn apply(&self, target: &mut Transform, t: f32) {
+		let height = (t * PI).sin() * WAVE_AMPL;
+		target.translation = self.base + Vec3::Y * height;
 	}
 	
-	/// Returns a float of how completed the animation is.
-	pub fn scale(&self) -> f32 {
-		self.d.as_secs_f32() / FLIP_ANIM_TIME.as_secs_f32()
+	fn finish(&self, target: &mut Transform) {
+		target.translation = self.base;
 	}
 }
 
-/// The animation for the wave that the letters do on win.
-#[derive(Component)]
-pub struct WaveAnim {
-	/// Elapsed duration.
-	d: Duration,
+/// Scales the height of an entity, like a tile turning to or away from the player.
+pub struct SquashLens {
+	/// The starting height scale.
+	pub from: f32,
+	/// The ending height scale.
+	pub to: f32,
 }
 
-impl WaveAnim {
-	/// Constructs a new [`WaveAnim`]
-	pub fn new() -> Self {
-		Self {
-			d: Duration::ZERO,
-		}
-	}
+impl Lens for SquashLens {
+	type Target = Transform;
 	
-	/// Ticks by duration, then returns weather it finished.
-	pub fn tick(&mut self, dur: Duration) -> bool {
-		self.d += dur;
-		self.d > WAVE_ANIM_TIME
+	fn apply(&self, target: &mut Transform, t: f32) {
+		target.scale.y = self.from + (self.to - self.from) * t;
 	}
+}
+
+/// Scales an entity evenly, like a banner popping in.
+pub struct ScaleLens {
+	/// The starting scale.
+	pub from: f32,
+	/// The ending scale.
+	pub to: f32,
+}
+
+impl Lens for ScaleLens {
+	type Target = Transform;
 	
-	/// Returns a float of how completed the animation is.
-	pub fn scale(&self) -> f32 {
-		self.d.as_secs_f32() / WAVE_ANIM_TIME.as_secs_f32()
+	fn apply(&self, target: &mut Transform, t: f32) {
+		target.scale = Vec3::splat(self.from + (self.to - self.from) * t);
 	}
 }
@@ -0,0 +1,121 @@
+use std::time::Duration;
+use bevy::prelude::*;
+use crate::{all_correct, SysLabel, TILE_SIZE, TILE_TOTAL, TileType, title_height};
+use crate::anim::{ScaleLens, SquashLens};
+use crate::components::{AnimSettings, GameSettings, Title, UnknownAnswer};
+use crate::events::EndFlipAnim;
+use crate::locale::Localization;
+use crate::theme::Theme;
+use crate::tween::{Ease, Tween};
+
+const BANNER_ANIM_TIME: Duration = Duration::from_millis(400);
+const BANNER_TEXT_SIZE: f32 = 40.0;
+/// The banner is drawn over the board.
+const BANNER_Z: f32 = 5.0;
+
+pub struct BannerPlugin;
+
+impl Plugin for BannerPlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.add_system(win_banner
+				.label(SysLabel::Anim)
+				.after(SysLabel::Input)
+			)
+		;
+	}
+}
+
+/// The message shown when the game is won.
+#[derive(Component)]
+pub struct Banner;
+
+/// Shows how well the player did once the winning row is revealed, in place of the title.
+/// Like the win sound, this waits for the flip instead of using [`GameWin`](crate::events::GameWin).
+fn win_banner(
+	mut commands: Commands,
+	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+	title_q: Query<Entity, With<Title>>,
+	unknown_answer: Option<Res<UnknownAnswer>>,
+	settings: Res<GameSettings>,
+	anim_settings: Res<AnimSettings>,
+	loc: Res<Localization>,
+	theme: Res<Theme>,
+	asset_server: Res<AssetServer>,
+) {
+	// Without a real answer, like when the player colors the bot's guesses, nobody won.
+	if unknown_answer.is_some() { return; }
+	
+	for end_flip_anim in end_flip_anim_r.iter() {
+		let end_flip_anim: &EndFlipAnim = end_flip_anim;
+		if !all_correct(&end_flip_anim.correctness) { continue; }
+		
+		let anim_time = if anim_settings.reduced_motion { Duration::ZERO } else { BANNER_ANIM_TIME };
+		
+		for title in title_q.iter() {
+			let squash = SquashLens { from: 1.0, to: 0.0 };
+			commands.entity(title).insert(Tween::new(squash, anim_time / 2).with_ease(Ease::QuadIn));
+		}
+		
+		let message = loc.get(win_message_key(end_flip_anim.row, settings.guesses));
+		let width = (settings.word_length as f32 * TILE_TOTAL).max(TILE_SIZE * 4.0);
+		let text_style = TextStyle {
+			font: asset_server.load("fonts/Swansea.ttf"),
+			font_size: BANNER_TEXT_SIZE,
+			color: theme.letter_color(TileType::Correct),
+		};
+		let alignment = TextAlignment {
+			vertical: VerticalAlign::Center,
+			horizontal: HorizontalAlign::Center,
+		};
+		
+		commands
+			.spawn(SpriteBundle {
+				sprite: Sprite {
+					color: theme.tile_color(TileType::Correct),
+					custom_size: Some(Vec2::new(width, TILE_SIZE * 0.8)),
+					..Default::default()
+				},
+				transform: Transform {
+					translation: Vec3::new(0.0, title_height(&settings), BANNER_Z),
+					scale: Vec3::ZERO,
+					..Default::default()
+				},
+				..Default::default()
+			})
+			.with_children(|c| {
+				c.spawn(Text2dBundle {
+					text: Text::from_section(message, text_style).with_alignment(alignment),
+					transform: Transform::from_translation(Vec3::new(0.0, BANNER_TEXT_SIZE * 0.27, 1.0)),
+					..Default::default()
+				});
+			})
+			.insert(Tween::new(ScaleLens { from: 0.0, to: 1.0 }, anim_time)
+				.with_delay(anim_time / 2)
+				.with_ease(Ease::BackOut)
+			)
+			.insert(Banner)
+			.insert(Name::new("Banner"));
+	}
+}
+
+/// Gets the localization key of the win message for winning on `row`.
+/// The first five rows each have their own message, and the last row is always a close call.
+pub fn win_message_key(row: usize, guesses: usize) -> &'static str {
+	const TIERS: [&str; 5] = ["win-1", "win-2", "win-3", "win-4", "win-5"];
+	
+	if row + 1 == guesses && row > 0 {
+		"win-last"
+	} else {
+		TIERS[row.min(TIERS.len() - 1)]
+	}
+}
+
+#[test]
+fn test_win_message_key() {
+	assert_eq!(win_message_key(0, 6), "win-1");
+	assert_eq!(win_message_key(4, 6), "win-5");
+	assert_eq!(win_message_key(5, 6), "win-last");
+	assert_eq!(win_message_key(7, 10), "win-5");
+	assert_eq!(win_message_key(0, 1), "win-1");
+}
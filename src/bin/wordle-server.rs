@@ -0,0 +1,398 @@
+//! Hosts a race between players on a network.
+//!
+//! Every player that joins gets the same answer, and sees the colours of the other players' guesses.
+//! The server scores each guess itself, and the first to solve the word wins the round. A new round then starts.
+//! If nobody has solved it in time, the round is a draw.
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+use clap::{CommandFactory, Parser, ValueEnum};
+use clap::error::ErrorKind;
+use rand::prelude::*;
+use wordle::dictionary::load_dictionary;
+use wordle::language::{Language, LanguagePack};
+use wordle::protocol::{ClientMessage, Mark, Rejection, ServerMessage, score, solved};
+
+/// How often the server checks whether the round has run out of time.
+const TICK: Duration = Duration::from_millis(100);
+
+/// A server for racing other players at Wordle.
+#[derive(Parser)]
+#[command(name = "wordle-server")]
+struct Args {
+	/// The address to listen on.
+	#[arg(long, default_value = "0.0.0.0:7878")]
+	address: String,
+	/// The language of the answers. Players have to play in the same language.
+	#[arg(long, value_enum, default_value_t = Language::En)]
+	language: Language,
+	/// The number of letters in the word.
+	#[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u8).range(2..=10))]
+	word_length: u8,
+	/// The number of guesses each player gets.
+	#[arg(long, default_value_t = 6, value_parser = clap::value_parser!(u8).range(1..=10))]
+	guesses: u8,
+	/// The number of seconds a round lasts before it is a draw.
+	#[arg(long, default_value_t = 300, value_parser = clap::value_parser!(u64).range(1..))]
+	round_time: u64,
+	/// A word list to use instead of the built in one. One word per line. Guesses have to be in it.
+	#[arg(long, value_name = "PATH")]
+	dictionary: Option<PathBuf>,
+	/// Seed used to pick the answers. Random if not set.
+	#[arg(long)]
+	seed: Option<u64>,
+}
+
+/// The game that every player shares.
+#[derive(Clone)]
+#[derive(Debug)]
+struct Config {
+	language: Language,
+	guesses: usize,
+	/// The words that can be guessed, and picked as the answer. Sorted, so that they can be binary searched.
+	words: Vec<String>,
+	seed: u64,
+	/// How long players have to solve each word.
+	round_time: Duration,
+}
+
+struct Player {
+	name: String,
+	/// Messages for the player's writer thread.
+	outbox: Sender<ServerMessage>,
+	/// The marks of each guess so far this round.
+	rows: Vec<Vec<Mark>>,
+}
+
+/// The state of the race.
+struct Race {
+	config: Config,
+	rng: StdRng,
+	/// Counts up each time a new answer is picked.
+	round: u32,
+	answer: String,
+	/// When the round ends in a draw, if nobody has solved it.
+	deadline: Instant,
+	next_id: u32,
+	players: BTreeMap<u32, Player>,
+}
+
+fn main() {
+	let args = Args::parse();
+	let pack = LanguagePack::built_in(args.language);
+	let config = Config {
+		language: args.language,
+		guesses: args.guesses as usize,
+		words: load_dictionary(args.dictionary.as_deref(), args.word_length as usize, &pack),
+		seed: args.seed.unwrap_or_else(rand::random),
+		round_time: Duration::from_secs(args.round_time),
+	};
+	if config.words.is_empty() {
+		error(format!("the dictionary has no {} letter words", args.word_length));
+	}
+	
+	let listener = TcpListener::bind(&args.address)
+		.unwrap_or_else(|e| error(format!("could not listen on {}: {}", args.address, e)));
+	println!("listening on {}", listener.local_addr().unwrap());
+	
+	serve(listener, config);
+}
+
+/// Prints an error with the usage, and exits.
+fn error(message: String) -> ! {
+	Args::command().error(ErrorKind::ValueValidation, message).exit()
+}
+
+/// Accepts players forever. Each player is handled on its own thread.
+fn serve(listener: TcpListener, config: Config) {
+	let race = Arc::new(Mutex::new(Race::new(config)));
+	
+	// A player that stops guessing can't hold up the others for longer than a round.
+	let ticker = race.clone();
+	thread::spawn(move || loop {
+		thread::sleep(TICK);
+		ticker.lock().unwrap().check_deadline(Instant::now());
+	});
+	
+	for stream in listener.incoming() {
+		let stream = match stream {
+			Ok(stream) => stream,
+			Err(_) => continue,
+		};
+		let race = race.clone();
+		thread::spawn(move || handle_player(stream, race));
+	}
+}
+
+fn handle_player(stream: TcpStream, race: Arc<Mutex<Race>>) {
+	let reader = match stream.try_clone() {
+		Ok(reader) => reader,
+		Err(_) => return,
+	};
+	let mut lines = BufReader::new(reader).lines();
+	
+	// The first message has to be a join, in the race's language.
+	let (language, name) = match lines.next().and_then(|line| line.ok()).and_then(|line| ClientMessage::parse(&line)) {
+		Some(ClientMessage::Join { language, name }) => (language, name),
+		_ => return,
+	};
+	let expected = race.lock().unwrap().config.language;
+	if Language::from_str(&language, true).ok() != Some(expected) {
+		let reason = Rejection::Language(language_code(expected));
+		let _ = writeln!(&stream, "{}", ServerMessage::Rejected { reason });
+		return;
+	}
+	
+	// Messages are written on their own thread, so a slow player doesn't hold up the race.
+	let (outbox, messages) = channel();
+	thread::spawn(move || write_messages(stream, messages));
+	let id = race.lock().unwrap().join(name, outbox);
+	
+	for line in lines {
+		let line = match line {
+			Ok(line) => line,
+			Err(_) => break,
+		};
+		if let Some(ClientMessage::Guess { round, word }) = ClientMessage::parse(&line) {
+			race.lock().unwrap().guess(id, round, &word);
+		}
+	}
+	
+	race.lock().unwrap().leave(id);
+}
+
+/// Writes a player's messages until they leave.
+/// If the player can't be written to, the connection is closed, so their reading thread makes them leave.
+fn write_messages(mut stream: TcpStream, messages: Receiver<ServerMessage>) {
+	for message in messages {
+		if writeln!(stream, "{}", message).is_err() {
+			let _ = stream.shutdown(Shutdown::Both);
+			return;
+		}
+	}
+}
+
+/// Gets the name of a language, as it is given on the command line.
+fn language_code(language: Language) -> String {
+	language.to_possible_value().unwrap().get_name().to_owned()
+}
+
+impl Race {
+	fn new(config: Config) -> Self {
+		let mut rng = StdRng::seed_from_u64(config.seed);
+		let answer = config.words.choose(&mut rng).unwrap().clone();
+		Race {
+			deadline: Instant::now() + config.round_time,
+			config,
+			rng,
+			round: 1,
+			answer,
+			next_id: 1,
+			players: BTreeMap::new(),
+		}
+	}
+	
+	/// Adds a player, and catches them up on the round so far.
+	fn join(&mut self, name: String, outbox: Sender<ServerMessage>) -> u32 {
+		let id = self.next_id;
+		self.next_id += 1;
+		
+		let _ = outbox.send(ServerMessage::Welcome {
+			id,
+			round: self.round,
+			word_length: self.answer.chars().count(),
+			guesses: self.config.guesses,
+		});
+		for (other_id, other) in self.players.iter() {
+			let _ = outbox.send(ServerMessage::Joined { id: *other_id, name: other.name.clone() });
+			for marks in other.rows.iter() {
+				let _ = outbox.send(ServerMessage::Progress { id: *other_id, marks: marks.clone() });
+			}
+		}
+		
+		println!("{} joined as player {}", name, id);
+		self.players.insert(id, Player { name: name.clone(), outbox, rows: Vec::new() });
+		self.broadcast(ServerMessage::Joined { id, name });
+		id
+	}
+	
+	/// Scores a player's guess and shares the marks, then checks if the round is over.
+	/// Guesses made in an earlier round, or that aren't in the word list, are rejected.
+	fn guess(&mut self, id: u32, round: u32, word: &str) {
+		let guesses = self.config.guesses;
+		let player = match self.players.get_mut(&id) {
+			Some(player) => player,
+			None => return,
+		};
+		let rejection = if round != self.round || player.rows.len() >= guesses {
+			Some(Rejection::RoundOver)
+		} else if self.config.words.binary_search_by(|w| w.as_str().cmp(word)).is_err() {
+			Some(Rejection::UnknownWord)
+		} else {
+			None
+		};
+		if let Some(reason) = rejection {
+			let _ = player.outbox.send(ServerMessage::Rejected { reason });
+			return;
+		}
+		
+		let marks = score(&self.answer, word);
+		player.rows.push(marks.clone());
+		let won = solved(&marks);
+		self.broadcast(ServerMessage::Progress { id, marks });
+		
+		if won {
+			self.broadcast(ServerMessage::Winner { id, answer: self.answer.clone() });
+			self.next_round();
+		} else if self.players.values().all(|player| player.rows.len() >= guesses) {
+			self.broadcast(ServerMessage::Draw { answer: self.answer.clone() });
+			self.next_round();
+		}
+	}
+	
+	/// Ends the round in a draw if it has run out of time.
+	/// Without any players, the clock only starts once someone can play.
+	fn check_deadline(&mut self, now: Instant) {
+		if now < self.deadline { return; }
+		if self.players.is_empty() {
+			self.deadline = now + self.config.round_time;
+			return;
+		}
+		
+		println!("round {} ran out of time", self.round);
+		self.broadcast(ServerMessage::Draw { answer: self.answer.clone() });
+		self.next_round();
+	}
+	
+	/// Picks a new answer, clears everyone's guesses, and restarts the clock.
+	fn next_round(&mut self) {
+		self.round += 1;
+		self.answer = self.config.words.choose(&mut self.rng).unwrap().clone();
+		self.deadline = Instant::now() + self.config.round_time;
+		for player in self.players.values_mut() {
+			player.rows.clear();
+		}
+		
+		println!("round {} started", self.round);
+		self.broadcast(ServerMessage::Round { round: self.round });
+	}
+	
+	fn leave(&mut self, id: u32) {
+		if let Some(player) = self.players.remove(&id) {
+			println!("{} left", player.name);
+			self.broadcast(ServerMessage::Left { id });
+		}
+	}
+	
+	/// Queues a message for every player. Errors are ignored, as the player's thread finds out when it next reads.
+	fn broadcast(&self, message: ServerMessage) {
+		for player in self.players.values() {
+			let _ = player.outbox.send(message.clone());
+		}
+	}
+}
+
+#[cfg(test)]
+fn test_config(round_time: Duration) -> Config {
+	Config {
+		language: Language::En,
+		guesses: 6,
+		words: vec!["crane".to_owned(), "slate".to_owned()],
+		seed: 42,
+		round_time,
+	}
+}
+
+/// Joins a test server, and returns the stream along with the messages it gets.
+#[cfg(test)]
+fn test_connect(addr: std::net::SocketAddr, language: &str, name: &str) -> (TcpStream, impl Iterator<Item = ServerMessage>) {
+	let mut stream = TcpStream::connect(addr).unwrap();
+	stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+	writeln!(stream, "{}", ClientMessage::Join { language: language.to_owned(), name: name.to_owned() }).unwrap();
+	let lines = BufReader::new(stream.try_clone().unwrap()).lines();
+	(stream, lines.map(|line| ServerMessage::parse(&line.unwrap()).unwrap()))
+}
+	
+#[test]
+fn test_race_on_localhost() {
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let addr = listener.local_addr().unwrap();
+	let config = test_config(Duration::from_secs(60));
+	// The server picks its answers the same way.
+	let mut rng = StdRng::seed_from_u64(config.seed);
+	let answer = config.words.choose(&mut rng).unwrap().clone();
+	let answer_2 = config.words.choose(&mut rng).unwrap().clone();
+	thread::spawn(move || serve(listener, config));
+	
+	let (_ada, mut ada_r) = test_connect(addr, "en", "Ada");
+	assert_eq!(ada_r.next(), Some(ServerMessage::Welcome { id: 1, round: 1, word_length: 5, guesses: 6 }));
+	assert_eq!(ada_r.next(), Some(ServerMessage::Joined { id: 1, name: "Ada".to_owned() }));
+	
+	let (_eve, mut eve_r) = test_connect(addr, "fr", "Eve");
+	assert_eq!(eve_r.next(), Some(ServerMessage::Rejected { reason: Rejection::Language("en".to_owned()) }));
+	
+	let (mut bob, mut bob_r) = test_connect(addr, "en", "Bob");
+	assert_eq!(bob_r.next(), Some(ServerMessage::Welcome { id: 2, round: 1, word_length: 5, guesses: 6 }));
+	assert_eq!(bob_r.next(), Some(ServerMessage::Joined { id: 1, name: "Ada".to_owned() }));
+	assert_eq!(bob_r.next(), Some(ServerMessage::Joined { id: 2, name: "Bob".to_owned() }));
+	assert_eq!(ada_r.next(), Some(ServerMessage::Joined { id: 2, name: "Bob".to_owned() }));
+	
+	// Words that aren't in the word list are only turned down, and don't use up a guess.
+	writeln!(bob, "{}", ClientMessage::Guess { round: 1, word: "zzzzz".to_owned() }).unwrap();
+	assert_eq!(bob_r.next(), Some(ServerMessage::Rejected { reason: Rejection::UnknownWord }));
+	
+	// The server scores the word, so a wrong guess can't win.
+	let wrong = if answer == "crane" { "slate" } else { "crane" };
+	writeln!(bob, "{}", ClientMessage::Guess { round: 1, word: wrong.to_owned() }).unwrap();
+	assert_eq!(ada_r.next(), Some(ServerMessage::Progress { id: 2, marks: score(&answer, wrong) }));
+	assert_eq!(bob_r.next(), Some(ServerMessage::Progress { id: 2, marks: score(&answer, wrong) }));
+	
+	writeln!(bob, "{}", ClientMessage::Guess { round: 1, word: answer.clone() }).unwrap();
+	assert_eq!(ada_r.next(), Some(ServerMessage::Progress { id: 2, marks: vec![Mark::Correct; 5] }));
+	assert_eq!(ada_r.next(), Some(ServerMessage::Winner { id: 2, answer: answer.clone() }));
+	assert_eq!(ada_r.next(), Some(ServerMessage::Round { round: 2 }));
+	
+	// Late guesses for the last round don't count in the new one.
+	writeln!(bob, "{}", ClientMessage::Guess { round: 1, word: answer.clone() }).unwrap();
+	writeln!(bob, "{}", ClientMessage::Guess { round: 2, word: wrong.to_owned() }).unwrap();
+	assert_eq!(ada_r.next(), Some(ServerMessage::Progress { id: 2, marks: score(&answer_2, wrong) }));
+	let bob_rest: Vec<_> = bob_r.take(5).collect();
+	assert_eq!(bob_rest[3..], [
+		ServerMessage::Rejected { reason: Rejection::RoundOver },
+		ServerMessage::Progress { id: 2, marks: score(&answer_2, wrong) },
+	]);
+}
+
+#[test]
+fn test_idle_player() {
+	let round_time = Duration::from_secs(1);
+	let start = Instant::now();
+	let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+	let addr = listener.local_addr().unwrap();
+	let config = test_config(round_time);
+	let answer = config.words.choose(&mut StdRng::seed_from_u64(config.seed)).unwrap().clone();
+	let wrong = if answer == "crane" { "slate" } else { "crane" };
+	thread::spawn(move || serve(listener, config));
+	
+	// Ada joins, but never guesses.
+	let (_ada, ada_r) = test_connect(addr, "en", "Ada");
+	let (mut bob, bob_r) = test_connect(addr, "en", "Bob");
+	for _ in 0..6 {
+		writeln!(bob, "{}", ClientMessage::Guess { round: 1, word: wrong.to_owned() }).unwrap();
+	}
+	
+	// Bob is out of guesses, but the round only ends once it runs out of time.
+	let mut ada_r = ada_r.skip_while(|message| !matches!(message, ServerMessage::Draw { .. }));
+	assert_eq!(ada_r.next(), Some(ServerMessage::Draw { answer: answer.clone() }));
+	assert!(start.elapsed() >= round_time);
+	assert_eq!(ada_r.next(), Some(ServerMessage::Round { round: 2 }));
+	
+	let progress = bob_r.filter(|message| matches!(message, ServerMessage::Progress { id: 2, .. })).take(6).count();
+	assert_eq!(progress, 6);
+}
@@ -0,0 +1,315 @@
+//! Bot mode: the player picks a word, and the computer guesses it.
+//!
+//! The bot types its guesses through the same [`InputBuffer`] as the player, so they are checked and revealed like any
+//! other guess. If the player gave a secret word, the guesses are colored automatically. Otherwise the player colors
+//! each tile by clicking it, or pressing its number, and the colors are checked against the words that could still be
+//! the answer. The answer is then never really known, so it isn't revealed at the end.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+use bevy::prelude::*;
+use wordle::util::upper;
+use crate::{all_correct, correctness, get_tile_pos, SysLabel, TILE_SIZE, TileType};
+use crate::accessibility::{Announce, describe_state};
+use crate::components::{BotSettings, Cursor, GameOver, GameSettings, InputBuffer, Pause, Tile, TileAssets, TileMap, UnknownAnswer, Word, WordDic};
+use crate::events::{EndFlipAnim, Guess, InputAction};
+use crate::layout::ScreenLayout;
+use crate::locale::Localization;
+use crate::theme::Theme;
+use crate::toast::ShowToast;
+
+/// How long the bot takes to type each letter.
+const BOT_KEY_DELAY: Duration = Duration::from_millis(150);
+/// How many words are compared when picking a guess. Larger lists are sampled, so the first guess doesn't take long.
+const SEARCH_LIMIT: usize = 200;
+/// The keys that color each tile of the guess, for players without a mouse.
+const TILE_KEYS: [KeyCode; 10] = [
+	KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4, KeyCode::Key5,
+	KeyCode::Key6, KeyCode::Key7, KeyCode::Key8, KeyCode::Key9, KeyCode::Key0,
+];
+
+
+pub struct BotPlugin;
+
+impl Plugin for BotPlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.init_resource::<Bot>()
+			.add_system_set(SystemSet::new()
+				.before(SysLabel::Input)
+				.with_system(bot_turn)
+				.with_system(mark_tiles)
+				.with_system(submit_marks.after(mark_tiles))
+			)
+			.add_system(announce_end)
+		;
+	}
+}
+
+/// What the bot knows, and what it is doing.
+#[derive(Default)]
+#[derive(Resource)]
+pub struct Bot {
+	/// Set once the bot has been given the dictionary.
+	started: bool,
+	/// Whether the guesses are colored using the secret word.
+	auto: bool,
+	/// The words that could still be the answer.
+	candidates: Vec<String>,
+	/// The row the bot has guessed on.
+	row: Option<usize>,
+	/// The word the bot guessed on the row.
+	guess: String,
+	/// The colors the player has given each tile of the guess.
+	marks: Vec<TileType>,
+	/// Set once the tiles of the guess show the marks.
+	shown: bool,
+	/// The keys the bot still has to type.
+	pending: VecDeque<InputAction>,
+	/// Counts down to the next key.
+	key_timer: Timer,
+}
+
+impl Bot {
+	/// Returns whether the guess has been typed and is waiting for the player to color it.
+	fn waiting_for_marks(&self, cursor: &Cursor) -> bool {
+		!self.auto && self.row == Some(cursor.y) && self.pending.is_empty() && !self.guess.is_empty()
+	}
+}
+
+/// Picks the word that splits the candidates into the smallest groups, on average.
+pub fn best_guess(candidates: &[String]) -> Option<&str> {
+	let stride = (candidates.len() / SEARCH_LIMIT).max(1);
+	
+	candidates.iter()
+		.step_by(stride)
+		.min_by_key(|guess| {
+			let mut groups: HashMap<Vec<TileType>, usize> = HashMap::new();
+			for answer in candidates.iter().step_by(stride) {
+				*groups.entry(correctness(answer, guess)).or_default() += 1;
+			}
+			groups.values().map(|n| n * n).sum::<usize>()
+		})
+		.map(|guess| guess.as_str())
+}
+
+/// Finds a word that would give the guess the player's colors, or [`None`] if no word could, like after a misclick.
+pub fn marks_fit<'a>(candidates: &'a [String], guess: &str, marks: &[TileType]) -> Option<&'a str> {
+	candidates.iter()
+		.find(|candidate| correctness(candidate, guess) == marks)
+		.map(|candidate| candidate.as_str())
+}
+
+/// The next color of a tile as the player marks it: grey, then yellow, then green.
+fn next_mark(tt: TileType) -> TileType {
+	match tt {
+		TileType::Wrong => TileType::Close,
+		TileType::Close => TileType::Correct,
+		_ => TileType::Wrong,
+	}
+}
+
+/// Picks a guess for each row, and types it.
+fn bot_turn(
+	mut commands: Commands,
+	mut guess_r: EventReader<Guess>,
+	mut bot: ResMut<Bot>,
+	mut word: ResMut<Word>,
+	mut input_buffer: ResMut<InputBuffer>,
+	dic: Res<WordDic>,
+	cursor: Res<Cursor>,
+	game_over: Res<GameOver>,
+	pause: Res<Pause>,
+	bot_settings: Res<BotSettings>,
+	time: Res<Time>,
+	loc: Res<Localization>,
+	mut toast_w: EventWriter<ShowToast>,
+) {
+	if !bot.started {
+		bot.started = true;
+		bot.candidates = dic.0.clone();
+		bot.key_timer = Timer::new(BOT_KEY_DELAY, TimerMode::Repeating);
+		
+		match &bot_settings.secret {
+			Some(secret) if dic.binary_search(secret).is_ok() => {
+				bot.auto = true;
+				word.0 = secret.clone();
+			}
+			Some(secret) => {
+				toast_w.send(ShowToast(loc.format("bot-unknown-secret", &[("word", &secret.to_uppercase())])));
+				toast_w.send(ShowToast(loc.get("bot-mark-help").to_owned()));
+			}
+			None => toast_w.send(ShowToast(loc.get("bot-mark-help").to_owned())),
+		}
+		if !bot.auto {
+			commands.insert_resource(UnknownAnswer);
+		}
+	}
+	
+	// Only keep the words that would have given the same colors.
+	for guess in guess_r.iter() {
+		bot.candidates.retain(|c| correctness(c, &guess.word) == guess.correctness);
+	}
+	
+	if game_over.0 || pause.paused() { return; }
+	
+	if bot.row != Some(cursor.y) {
+		bot.row = Some(cursor.y);
+		
+		let guess = match best_guess(&bot.candidates) {
+			Some(guess) => guess.to_owned(),
+			None => {
+				bot.guess.clear();
+				toast_w.send(ShowToast(loc.get("bot-stumped").to_owned()));
+				return;
+			}
+		};
+		
+		let mut pending: VecDeque<_> = guess.chars().map(InputAction::Letter).collect();
+		if bot.auto {
+			pending.push_back(InputAction::Submit);
+		}
+		bot.pending = pending;
+		bot.marks = vec![TileType::Wrong; guess.chars().count()];
+		bot.shown = false;
+		bot.guess = guess;
+	}
+	
+	if bot.pending.is_empty() { return; }
+	if bot.key_timer.tick(time.delta()).just_finished() {
+		let key = bot.pending.pop_front().unwrap();
+		input_buffer.push_back(key);
+	}
+}
+
+/// Colors the tiles of the bot's guess grey once it is typed, then cycles the color of a tile when it is clicked,
+/// tapped, or its number is pressed.
+fn mark_tiles(
+	mut bot: ResMut<Bot>,
+	mut tiles_q: Query<(&mut Handle<Image>, &mut Sprite), With<Tile>>,
+	mouse: Res<Input<MouseButton>>,
+	keys: Res<Input<KeyCode>>,
+	touches: Res<Touches>,
+	windows: Res<Windows>,
+	layout: Res<ScreenLayout>,
+	cursor: Res<Cursor>,
+	tile_map: Res<TileMap>,
+	tile_assets: Res<TileAssets>,
+	settings: Res<GameSettings>,
+	theme: Res<Theme>,
+	loc: Res<Localization>,
+	mut announce_w: EventWriter<Announce>,
+) {
+	if !bot.waiting_for_marks(&cursor) { return; }
+	
+	let mut marked = Vec::new();
+	
+	let mut clicks = Vec::new();
+	if mouse.just_pressed(MouseButton::Left) {
+		clicks.extend(windows.get_primary().and_then(|window| window.cursor_position()));
+	}
+	clicks.extend(touches.iter_just_released().map(|touch| layout.touch_to_window(touch.position())));
+	for click in clicks {
+		let click = layout.to_world(click);
+		marked.extend((0..settings.word_length).find(|x| {
+			let tile_pos = get_tile_pos(*x, cursor.y, &settings).truncate();
+			(click - tile_pos).abs().max_element() <= TILE_SIZE / 2.0
+		}));
+	}
+	
+	marked.extend(TILE_KEYS.iter().take(settings.word_length).position(|key| keys.just_pressed(*key)));
+	
+	for x in marked.iter().copied() {
+		let mark = next_mark(bot.marks[x]);
+		bot.marks[x] = mark;
+		
+		let letter = bot.guess.chars().nth(x).map(upper).unwrap_or_default();
+		announce_w.send(Announce(loc.format("a11y-tile", &[
+			("letter", &letter),
+			("state", &describe_state(mark, &loc)),
+			("position", &(x + 1)),
+		])));
+	}
+	
+	if bot.shown && marked.is_empty() { return; }
+	bot.shown = true;
+	
+	for (x, mark) in bot.marks.iter().enumerate() {
+		if let Ok((mut texture, mut sprite)) = tiles_q.get_mut(tile_map[cursor.y][x]) {
+			*texture = tile_assets.colored.clone();
+			sprite.color = theme.tile_color(*mark);
+		}
+	}
+}
+
+/// Submits the bot's guess with the player's colors, as long as some word could give those colors.
+/// The answer is then set to one of those words, so that the guess is revealed with the same colors.
+fn submit_marks(
+	mut input_r: EventReader<InputAction>,
+	bot: Res<Bot>,
+	mut word: ResMut<Word>,
+	mut input_buffer: ResMut<InputBuffer>,
+	cursor: Res<Cursor>,
+	game_over: Res<GameOver>,
+	loc: Res<Localization>,
+	mut toast_w: EventWriter<ShowToast>,
+) {
+	let submitted = input_r.iter().filter(|input| **input == InputAction::Submit).count() > 0;
+	if !submitted || game_over.0 || !bot.waiting_for_marks(&cursor) { return; }
+	
+	match marks_fit(&bot.candidates, &bot.guess, &bot.marks) {
+		Some(fits) => {
+			word.0 = fits.to_owned();
+			input_buffer.push_back(InputAction::Submit);
+		}
+		None => {
+			let guess: String = bot.guess.chars().map(upper).collect();
+			toast_w.send(ShowToast(loc.format("bot-no-word-fits", &[("word", &guess)])));
+		}
+	}
+}
+
+/// Says how the bot did when the player colored the guesses, in place of the banner and the answer.
+fn announce_end(
+	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+	bot: Res<Bot>,
+	settings: Res<GameSettings>,
+	loc: Res<Localization>,
+	mut toast_w: EventWriter<ShowToast>,
+) {
+	if bot.auto { return; }
+	
+	for end_flip_anim in end_flip_anim_r.iter() {
+		if all_correct(&end_flip_anim.correctness) {
+			toast_w.send(ShowToast(loc.format("bot-solved", &[("count", &(end_flip_anim.row + 1))])));
+		} else if end_flip_anim.row + 1 == settings.guesses {
+			toast_w.send(ShowToast(loc.format("bot-words-left", &[("count", &bot.candidates.len())])));
+		}
+	}
+}
+
+#[test]
+fn test_best_guess() {
+	let candidates: Vec<_> = ["cat", "cot", "cut", "dog"].iter().map(|s| s.to_string()).collect();
+	
+	// Every word splits these into groups of 1, 2 and 1, so the first one is picked.
+	assert_eq!(best_guess(&candidates), Some("cat"));
+	assert_eq!(best_guess(&candidates[3..]), Some("dog"));
+	assert_eq!(best_guess(&[]), None);
+	
+	// TAR gives each of the others different colors, where the rest leave two words looking the same.
+	let candidates: Vec<_> = ["cap", "cat", "car", "arc", "tar"].iter().map(|s| s.to_string()).collect();
+	assert_eq!(best_guess(&candidates), Some("tar"));
+}
+
+#[test]
+fn test_marks_fit() {
+	use TileType::*;
+	let candidates: Vec<_> = ["cat", "cot", "dog"].iter().map(|s| s.to_string()).collect();
+	
+	assert_eq!(marks_fit(&candidates, "cat", &[Correct, Wrong, Correct]), Some("cot"));
+	assert_eq!(marks_fit(&candidates, "cat", &[Correct; 3]), Some("cat"));
+	// A misclick that no word could give.
+	assert_eq!(marks_fit(&candidates, "cat", &[Close, Close, Close]), None);
+	assert_eq!(marks_fit(&candidates, "cat", &[Wrong, Correct, Wrong]), None);
+}
@@ -1,9 +1,14 @@
+use std::collections::VecDeque;
 use std::ops::{Deref, DerefMut};
 use std::fmt::Formatter;
+use std::path::PathBuf;
 use std::sync::Arc;
 use bevy::prelude::*;
-use bevy::ecs::schedule::{ShouldRun, SystemLabel};
-use crate::{Color, D_GREY, Entity, GREEN, Handle, Image, L_GREY, Quat, Vec3, YELLOW};
+use bevy::ecs::schedule::SystemLabel;
+use clap::ValueEnum;
+use rand::rngs::StdRng;
+use crate::{Entity, Handle, Image, TileType};
+use crate::events::{InputAction, InvalidReason};
 
 ///! Contains components and resources.
 #[derive(Copy, Clone)]
@@ -14,11 +19,149 @@ use crate::{Color, D_GREY, Entity, GREEN, Handle, Image, L_GREY, Quat, Vec3, YEL
 pub enum SysLabel {
 	Anim,
 	Input,
-	Logic,
 	Graphics,
 	Setup,
 }
 
+/// The settings of the current game, usually taken from the command line.
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct GameSettings {
+	/// The number of letters in each word.
+	pub word_length: usize,
+	/// The number of guesses the player gets.
+	pub guesses: usize,
+	/// Any revealed hints must be used in subsequent guesses.
+	pub hard: bool,
+	/// The seed used to pick the answer.
+	pub seed: u64,
+	/// Set when the seed was given, by `--seed` or `--daily`, so the answer may already be known.
+	pub seeded: bool,
+	/// A word list to use instead of the built in one.
+	pub dictionary: Option<PathBuf>,
+	/// How the game is played.
+	pub mode: GameMode,
+}
+
+impl Default for GameSettings {
+	fn default() -> Self {
+		GameSettings {
+			word_length: 5,
+			guesses: 6,
+			hard: false,
+			seed: 0,
+			seeded: false,
+			dictionary: None,
+			mode: GameMode::default(),
+		}
+	}
+}
+
+impl GameSettings {
+	/// Returns whether the game uses the usual word length, guesses and word list, with an answer nobody knows yet.
+	/// Records like the best time are only kept for these games, so that easier rules can't beat them.
+	pub fn is_standard(&self) -> bool {
+		let usual = GameSettings::default();
+		self.word_length == usual.word_length && self.guesses == usual.guesses && self.dictionary.is_none() && !self.seeded
+	}
+}
+
+/// The ways the game can be played.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug, Default)]
+#[derive(ValueEnum)]
+pub enum GameMode {
+	/// One player guesses the word.
+	#[default]
+	Classic,
+	/// Two players take turns guessing on the same board. The first to solve it wins.
+	Versus,
+	/// Solve as many words as possible before the time runs out.
+	TimeAttack,
+	/// Each solved word is followed by a new one, until a word is missed.
+	Endless,
+	/// The answer keeps changing to dodge the guesses, for as long as it can.
+	Evil,
+	/// The answer is known, and each row has to give the colors that are shown beside it.
+	Reverse,
+	/// The player picks a word, and the computer guesses it.
+	Bot,
+}
+
+/// Settings for the animations.
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct AnimSettings {
+	/// How fast animations play; 2.0 is twice as fast.
+	pub speed: f32,
+	/// Replaces movement with instant changes, for players that are sensitive to motion.
+	pub reduced_motion: bool,
+}
+
+impl Default for AnimSettings {
+	fn default() -> Self {
+		AnimSettings {
+			speed: 1.0,
+			reduced_motion: false,
+		}
+	}
+}
+
+/// Settings for the sound effects.
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct AudioSettings {
+	/// The volume, from 0.0 to 1.0.
+	pub volume: f32,
+	/// No sounds are played while muted.
+	pub muted: bool,
+}
+
+impl Default for AudioSettings {
+	fn default() -> Self {
+		AudioSettings {
+			volume: 0.5,
+			muted: false,
+		}
+	}
+}
+
+/// Settings for how the game is drawn.
+#[derive(Clone, Debug, Default)]
+#[derive(Resource)]
+pub struct DisplaySettings {
+	/// Marks correct and close tiles and keys with shapes, so that colour is not needed.
+	pub markers: bool,
+	/// Shows how long the game has taken.
+	pub timer: bool,
+}
+
+/// Present when the answer isn't really known, like when the player colors the bot's guesses by hand.
+/// The answer is then not revealed at the end, and there is no win banner.
+#[derive(Resource)]
+pub struct UnknownAnswer;
+
+/// Present in a race, where only the server knows the answer, so it scores each guess.
+#[derive(Default)]
+#[derive(Resource)]
+pub struct RemoteScoring {
+	/// A guess to send to the server.
+	pub outgoing: Option<String>,
+	/// The server's reply to the guess: its colors, or why it was turned down.
+	pub reply: Option<Result<Vec<TileType>, InvalidReason>>,
+	/// Set from when a guess is sent until its reply is used.
+	pub waiting: bool,
+}
+
+/// Settings for bot mode.
+#[derive(Clone, Debug, Default)]
+#[derive(Resource)]
+pub struct BotSettings {
+	/// The word for the bot to guess. Without it, the player colors in each guess by hand.
+	pub secret: Option<String>,
+}
+
 #[derive(Clone, Resource)]
 pub struct WordDic(pub Vec<String>);
 
@@ -40,6 +183,7 @@ impl DerefMut for WordDic {
 #[derive(Debug)]
 #[derive(Eq, PartialEq)]
 #[derive(Ord, PartialOrd)]
+#[derive(Hash)]
 pub enum TileType {
 	Correct,
 	Close,
@@ -47,17 +191,6 @@ pub enum TileType {
 	Default,
 }
 
-impl TileType {
-	pub fn color(&self) -> Color {
-		match self {
-			TileType::Default => *L_GREY,
-			TileType::Correct => *GREEN,
-			TileType::Close => *YELLOW,
-			TileType::Wrong => *D_GREY,
-		}
-	}
-}
-
 #[derive(Component)]
 pub struct Tile {
 	pub tt: TileType,
@@ -66,6 +199,23 @@ pub struct Tile {
 	pub y: u32,
 }
 
+/// A shape that shows the [`TileType`] of its parent tile or key.
+/// It is hidden unless [`DisplaySettings::markers`] is set.
+#[derive(Component)]
+pub struct Marker;
+
+/// A tile of the answer, which is shown when the game is lost.
+#[derive(Component)]
+pub struct Answer;
+
+/// Picks the answer of each game.
+#[derive(Resource)]
+pub struct AnswerRng(pub StdRng);
+
+/// The title above the board.
+#[derive(Component)]
+pub struct Title;
+
 #[derive(Clone, Resource)]
 pub struct TileAssets {
 	pub default: Handle<Image>,
@@ -98,6 +248,11 @@ impl DerefMut for Word {
 	}
 }
 
+/// Set once the game is won or every guess is used up. No more input is taken after that.
+#[derive(Default)]
+#[derive(Resource)]
+pub struct GameOver(pub bool);
+
 #[derive(Resource)]
 pub struct Cursor {
 	pub x: usize,
@@ -110,29 +265,47 @@ impl Cursor {
 		self.x = 0;
 	}
 	
-	pub fn next_char(&mut self) {
+	pub fn next_char(&mut self, word_length: usize) {
 		self.x += 1;
-		self.x = self.x.clamp(0, 5);
+		self.x = self.x.clamp(0, word_length);
+	}
+}
+
+/// Input that has not been applied to the board yet.
+#[derive(Default)]
+#[derive(Resource)]
+pub struct InputBuffer(VecDeque<InputAction>);
+
+impl Deref for InputBuffer {
+	type Target = VecDeque<InputAction>;
+	
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl DerefMut for InputBuffer {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0
 	}
 }
 
 #[derive(Resource)]
 pub struct TileMap {
-	tiles: [[Entity; 5]; 6],
+	tiles: Vec<Vec<Entity>>,
 }
 
 impl TileMap {
-	/// Creates a new null filled [`TileMap`].
-	/// Make sure to initialize all tiles.
-	pub fn null() -> Self {
+	/// Creates a new [`TileMap`] from rows of tiles.
+	pub fn new(tiles: Vec<Vec<Entity>>) -> Self {
 		TileMap {
-			tiles: [[Entity::from_bits(0); 5]; 6],
+			tiles,
 		}
 	}
 }
 
 impl Deref for TileMap {
-	type Target = [[Entity; 5]; 6];
+	type Target = Vec<Vec<Entity>>;
 	
 	fn deref(&self) -> &Self::Target {
 		&self.tiles
@@ -145,17 +318,6 @@ impl DerefMut for TileMap {
 	}
 }
 
-#[derive(Component)]
-pub struct ConfettiSpawner {
-	pub dir: Vec3,
-}
-
-#[derive(Component)]
-pub struct Confetti {
-	pub velocity: Vec3,
-	pub rot: Quat,
-}
-
 /// Can be used to pause the game as long as a [`PauseLock`] exists.
 /// This is done using reference counting.
 #[derive(Resource)]
@@ -182,8 +344,12 @@ impl Pause {
 #[derive(Clone)]
 pub struct PauseLock(Arc<()>);
 
-pub fn not_paused(
-	pause: Res<Pause>,
-) -> ShouldRun {
-	if pause.paused() { ShouldRun::No } else { ShouldRun::Yes }
+#[test]
+fn test_is_standard() {
+	assert!(GameSettings::default().is_standard());
+	assert!(GameSettings { hard: true, ..Default::default() }.is_standard());
+	assert!(!GameSettings { word_length: 2, ..Default::default() }.is_standard());
+	assert!(!GameSettings { guesses: 3, ..Default::default() }.is_standard());
+	assert!(!GameSettings { dictionary: Some(PathBuf::from("words.txt")), ..Default::default() }.is_standard());
+	assert!(!GameSettings { seeded: true, ..Default::default() }.is_standard());
 }
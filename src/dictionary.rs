@@ -0,0 +1,127 @@
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use crate::language::LanguagePack;
+
+/// The maximum number of malformed entries that get logged individually.
+const MAX_REPORTED: usize = 10;
+
+/// An entry of a word list that could not be used.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Eq, PartialEq)]
+pub struct Malformed {
+	/// The line number of the entry, starting at 1.
+	pub line: usize,
+	/// The entry, as it was written.
+	pub entry: String,
+	/// What is wrong with the entry.
+	pub problem: Problem,
+}
+
+/// What is wrong with a [`Malformed`] entry.
+#[derive(Copy, Clone)]
+#[derive(Debug)]
+#[derive(Eq, PartialEq)]
+pub enum Problem {
+	/// The entry does not have the right number of letters.
+	WrongLength,
+	/// The entry has characters that are not in the alphabet.
+	NotAlphabetic,
+	/// The entry is already in the list.
+	Duplicate,
+}
+
+impl Display for Malformed {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let problem = match self.problem {
+			Problem::WrongLength => "wrong length",
+			Problem::NotAlphabetic => "not alphabetic",
+			Problem::Duplicate => "duplicate",
+		};
+		write!(f, "line {}: {:?} ({})", self.line, self.entry, problem)
+	}
+}
+
+/// Parses a word list with one word per line.
+///
+/// Blank lines and lines starting with `#` are skipped. Words are lowercased and folded by the [`LanguagePack`].
+/// Returns the valid words, sorted so that they can be binary searched, along with any malformed entries.
+pub fn parse_word_list(raw: &str, word_length: usize, pack: &LanguagePack) -> (Vec<String>, Vec<Malformed>) {
+	let mut words = Vec::new();
+	let mut malformed = Vec::new();
+
+	for (idx, line) in raw.lines().enumerate() {
+		let entry = line.trim();
+		if entry.is_empty() || entry.starts_with('#') { continue; }
+		let word = pack.fold_word(entry);
+
+		let problem = if !word.chars().all(|c| pack.is_letter(c)) {
+			Some(Problem::NotAlphabetic)
+		} else if word.chars().count() != word_length {
+			Some(Problem::WrongLength)
+		} else {
+			None
+		};
+
+		match problem {
+			Some(problem) => malformed.push(Malformed { line: idx + 1, entry: entry.to_owned(), problem }),
+			None => words.push((word, idx + 1, entry)),
+		}
+	}
+
+	// Sort by word, keeping the first occurrence of a duplicate first.
+	words.sort();
+	let mut sorted: Vec<String> = Vec::with_capacity(words.len());
+	for (word, line, entry) in words {
+		if sorted.last() == Some(&word) {
+			malformed.push(Malformed { line, entry: entry.to_owned(), problem: Problem::Duplicate });
+			continue;
+		}
+		sorted.push(word);
+	}
+
+	malformed.sort_by_key(|m| m.line);
+	(sorted, malformed)
+}
+
+/// Reads and parses the word list at `path`.
+pub fn load_word_list(path: &Path, word_length: usize, pack: &LanguagePack) -> std::io::Result<(Vec<String>, Vec<Malformed>)> {
+	let raw = std::fs::read_to_string(path)?;
+	Ok(parse_word_list(&raw, word_length, pack))
+}
+
+/// Loads the word list at `path`, or the one from the [`LanguagePack`] if there is none or it can't be read.
+/// Malformed entries are printed to stderr, as this runs before the app, and its logging, is set up.
+pub fn load_dictionary(path: Option<&Path>, word_length: usize, pack: &LanguagePack) -> Vec<String> {
+	let (words, malformed) = match path {
+		Some(path) => load_word_list(path, word_length, pack).unwrap_or_else(|err| {
+			eprintln!("error: failed to read dictionary {}: {}; using the built in one", path.display(), err);
+			parse_word_list(pack.words, word_length, pack)
+		}),
+		None => parse_word_list(pack.words, word_length, pack),
+	};
+
+	for m in malformed.iter().take(MAX_REPORTED) {
+		eprintln!("warning: skipped dictionary entry {}", m);
+	}
+	if malformed.len() > MAX_REPORTED {
+		eprintln!("warning: skipped {} more dictionary entries", malformed.len() - MAX_REPORTED);
+	}
+
+	words
+}
+
+#[test]
+fn test_parse_word_list() {
+	use crate::language::Language;
+
+	let raw = "# comment\nWorld\nhello\n\nhel1o\nhi\nWORLD\n";
+	let (words, malformed) = parse_word_list(raw, 5, &LanguagePack::built_in(Language::En));
+
+	assert_eq!(words, ["hello", "world"]);
+	assert_eq!(malformed.iter().map(|m| (m.line, m.entry.as_str(), m.problem)).collect::<Vec<_>>(), [
+		(5, "hel1o", Problem::NotAlphabetic),
+		(6, "hi", Problem::WrongLength),
+		(7, "WORLD", Problem::Duplicate),
+	]);
+}
@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+use crate::{all_correct, SysLabel};
+use crate::components::GameSettings;
+use crate::events::{EndFlipAnim, NewGame};
+use crate::locale::Localization;
+use crate::stats::Stats;
+use crate::toast::ShowToast;
+
+const STREAK_TEXT_SIZE: f32 = 28.0;
+
+/// Endless mode: each solved word is followed by a new one, until a word is missed.
+pub struct EndlessPlugin;
+
+impl Plugin for EndlessPlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.init_resource::<Streak>()
+			.add_startup_system(setup_streak_text)
+			.add_system(count_guesses.after(SysLabel::Input))
+			.add_system(next_word.after(SysLabel::Input))
+			.add_system(end_streak.after(count_guesses))
+			.add_system(update_streak_text.after(count_guesses).after(next_word))
+		;
+	}
+}
+
+/// The score of the current run.
+#[derive(Default)]
+#[derive(Resource)]
+pub struct Streak {
+	/// How many words have been solved.
+	pub solved: u32,
+	/// How many guesses have been made, over every word.
+	pub guesses: u32,
+}
+
+impl Streak {
+	/// Returns whether this run beats the saved best: more words, or as many in fewer guesses.
+	pub fn beats(&self, stats: &Stats) -> bool {
+		self.solved > stats.best_streak
+			|| (self.solved == stats.best_streak && self.solved > 0 && self.guesses < stats.best_streak_guesses)
+	}
+}
+
+/// The text that shows the score.
+#[derive(Component)]
+struct StreakText;
+
+/// Shows the score at the top left, under the timer, as the top right is kept for the race's mini boards.
+fn setup_streak_text(
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+) {
+	commands
+		.spawn(TextBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				position: UiRect {
+					top: Val::Px(60.0),
+					left: Val::Px(20.0),
+					..Default::default()
+				},
+				..Default::default()
+			},
+			text: Text::from_section(
+				"",
+				TextStyle {
+					font: asset_server.load("fonts/Swansea.ttf"),
+					font_size: STREAK_TEXT_SIZE,
+					color: Color::GRAY,
+				},
+			),
+			..Default::default()
+		})
+		.insert(StreakText)
+		.insert(Name::new("Streak"));
+}
+
+/// Counts each guess once its row has been revealed, so the score doesn't give away the result.
+fn count_guesses(
+	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+	mut streak: ResMut<Streak>,
+) {
+	for end_flip_anim in end_flip_anim_r.iter() {
+		streak.guesses += 1;
+		if all_correct(&end_flip_anim.correctness) {
+			streak.solved += 1;
+		}
+	}
+}
+
+/// Loads the next word as soon as the winning row has been revealed.
+fn next_word(
+	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+	mut new_game_w: EventWriter<NewGame>,
+) {
+	for end_flip_anim in end_flip_anim_r.iter() {
+		if all_correct(&end_flip_anim.correctness) {
+			new_game_w.send(NewGame);
+		}
+	}
+}
+
+/// Shows the final score once the last row of a missed word is revealed, and saves it if it is the best yet.
+fn end_streak(
+	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+	streak: Res<Streak>,
+	mut stats: ResMut<Stats>,
+	settings: Res<GameSettings>,
+	loc: Res<Localization>,
+	mut toast_w: EventWriter<ShowToast>,
+) {
+	let missed = end_flip_anim_r.iter()
+		.any(|end_flip_anim| !all_correct(&end_flip_anim.correctness) && end_flip_anim.row + 1 == settings.guesses);
+	if !missed { return; }
+	
+	toast_w.send(ShowToast(loc.format("streak-over", &[("count", &streak.solved), ("guesses", &streak.guesses)])));
+	if streak.beats(&stats) {
+		stats.best_streak = streak.solved;
+		stats.best_streak_guesses = streak.guesses;
+		stats.save();
+		toast_w.send(ShowToast(loc.format("streak-best", &[("count", &streak.solved)])));
+	}
+}
+
+fn update_streak_text(
+	mut text_q: Query<&mut Text, With<StreakText>>,
+	streak: Res<Streak>,
+	stats: Res<Stats>,
+	loc: Res<Localization>,
+) {
+	if !streak.is_changed() && !stats.is_changed() { return; }
+	
+	for mut text in text_q.iter_mut() {
+		text.sections[0].value = loc.format("streak-status", &[
+			("count", &streak.solved),
+			("guesses", &streak.guesses),
+			("best", &stats.best_streak),
+		]);
+	}
+}
+
+#[test]
+fn test_streak_beats() {
+	let stats = Stats { best_streak: 3, best_streak_guesses: 12, ..Default::default() };
+	
+	assert!(Streak { solved: 4, guesses: 20 }.beats(&stats));
+	assert!(Streak { solved: 3, guesses: 11 }.beats(&stats));
+	assert!(!Streak { solved: 3, guesses: 12 }.beats(&stats));
+	assert!(!Streak { solved: 2, guesses: 5 }.beats(&stats));
+	assert!(!Streak { solved: 0, guesses: 6 }.beats(&Stats::default()));
+}
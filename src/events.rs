@@ -8,9 +8,12 @@ impl Plugin for Events {
 		app
 			.add_event::<Guess>()
 			.add_event::<InvalidGuess>()
+			.add_event::<InputAction>()
 			.add_event::<TypedLetter>()
 			.add_event::<EndFlipAnim>()
 			.add_event::<GameWin>()
+			.add_event::<GameLoss>()
+			.add_event::<NewGame>()
 		;
 	}
 }
@@ -21,7 +24,7 @@ pub struct Guess {
 	/// The word that was guessed.
 	pub word: String,
 	/// The correctness of the guess.
-	pub correctness: [TileType; 5],
+	pub correctness: Vec<TileType>,
 	/// The row that was guessed on.
 	pub row: usize,
 }
@@ -29,6 +32,27 @@ pub struct Guess {
 /// An event that is fired when the player makes an invalid guess.
 pub struct InvalidGuess {
 	pub row: usize,
+	/// Why the guess was rejected.
+	pub reason: InvalidReason,
+}
+
+/// The reason a guess was rejected.
+#[derive(Copy, Clone)]
+#[derive(Debug)]
+#[derive(Eq, PartialEq)]
+pub enum InvalidReason {
+	/// The row was not full.
+	NotEnoughLetters,
+	/// The guess is not in the dictionary.
+	NotInWordList,
+	/// Hard mode: the (lowercase) letter was revealed as correct, so it must stay at the index.
+	MustBeAt(char, usize),
+	/// Hard mode: the (lowercase) letter was revealed as close, so it must be used.
+	MustContain(char),
+	/// Reverse mode: the guess doesn't give the colors that the row needs.
+	WrongPattern,
+	/// Race: the round ended before the server got the guess.
+	RoundOver,
 }
 
 /// An event that is fired when the player types a letter.
@@ -39,16 +63,29 @@ pub struct TypedLetter {
 	pub y: usize,
 	/// If the letter was put into the
 	pub valid: bool,
-	/// The lowercase letter that was just typed
+	/// The uppercase letter that was just typed
 	pub letter: char,
 }
 
+/// An event that is fired when the player presses a key, on either the physical or on-screen keyboard.
+#[derive(Copy, Clone)]
+#[derive(Debug)]
+#[derive(Eq, PartialEq)]
+pub enum InputAction {
+	/// A (lowercase) letter of the alphabet was typed.
+	Letter(char),
+	/// The last letter should be removed.
+	Backspace,
+	/// The guess should be submitted.
+	Submit,
+}
+
 #[derive(Clone)]
 pub struct EndFlipAnim {
 	/// The word that was guessed.
 	pub word: String,
 	/// The correctness of the guess.
-	pub correctness: [TileType; 5],
+	pub correctness: Vec<TileType>,
 	/// The row that was guessed on.
 	pub row: usize,
 }
@@ -58,7 +95,7 @@ impl Into<EndFlipAnim> for &Guess {
 		EndFlipAnim {
 			row: self.row,
 			word: self.word.clone(),
-			correctness: self.correctness,
+			correctness: self.correctness.clone(),
 		}
 	}
 }
@@ -69,3 +106,9 @@ pub struct GameWin {
 	/// The row that the game was won on
 	pub row: usize,
 }
+
+/// An event that is fired when the last guess is used up without finding the word.
+pub struct GameLoss;
+
+/// An event that starts a new game with a new word, on the same board.
+pub struct NewGame;
@@ -0,0 +1,64 @@
+//! Evil mode, in the style of Absurdle: the answer isn't picked at the start.
+//!
+//! Instead, every word that fits the feedback so far is kept. For each guess, the words are split up by the feedback
+//! they would give, and the largest group is kept, so the player learns as little as possible.
+
+use std::collections::HashMap;
+use bevy::prelude::*;
+use crate::{correctness, TileType};
+
+/// The words that could still be the answer.
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct Candidates(pub Vec<String>);
+
+impl Candidates {
+	/// Keeps the words that give the least away for a guess, and returns the feedback that they give.
+	/// The answer should then be set to any of the remaining words.
+	pub fn narrow(&mut self, guess: &str) -> Vec<TileType> {
+		let mut buckets: HashMap<Vec<TileType>, Vec<String>> = HashMap::new();
+		for word in self.0.drain(..) {
+			buckets.entry(correctness(&word, guess)).or_default().push(word);
+		}
+		
+		// The largest group wins. Ties go to the feedback with the fewest hints, then to the earliest pattern, so that
+		// the same guesses always get the same feedback.
+		let (pattern, words) = buckets.into_iter()
+			.max_by(|(a_pattern, a_words), (b_pattern, b_words)| {
+				a_words.len().cmp(&b_words.len())
+					.then_with(|| hints(b_pattern).cmp(&hints(a_pattern)))
+					.then_with(|| b_pattern.cmp(a_pattern))
+			})
+			.expect("there are no candidates left");
+		
+		self.0 = words;
+		pattern
+	}
+}
+
+/// How much a feedback pattern gives away. A correct letter counts for more than a close one.
+pub fn hints(pattern: &[TileType]) -> usize {
+	pattern.iter()
+		.map(|tt| match tt {
+			TileType::Correct => 2,
+			TileType::Close => 1,
+			_ => 0,
+		})
+		.sum()
+}
+
+#[test]
+fn test_narrow() {
+	use TileType::*;
+	
+	let words = ["cat", "cot", "cut", "dog"].iter().map(|s| s.to_string()).collect();
+	let mut candidates = Candidates(words);
+	
+	// "cat" and "cot" share a pattern, so they beat "cut" and "dog" on their own.
+	assert_eq!(candidates.narrow("hut"), [Wrong, Wrong, Correct]);
+	assert_eq!(candidates.0, ["cat", "cot"]);
+	
+	// Guessing a candidate only wins once it is the last one.
+	assert_eq!(candidates.narrow("cat"), [Correct, Wrong, Correct]);
+	assert_eq!(candidates.narrow("cot"), [Correct, Correct, Correct]);
+}
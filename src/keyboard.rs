@@ -1,10 +1,23 @@
 use bevy::prelude::*;
+use clap::ValueEnum;
+use wordle::language::LanguagePack;
+use wordle::util::{lower, upper};
 use crate::Interaction::Clicked;
-use crate::{L_GREY, SysLabel, TileType};
-use crate::util::GetKeyCode;
+use crate::{SysLabel, TileType};
+use crate::components::Marker;
+use crate::events::InputAction;
+use crate::locale::Localization;
+use crate::theme::Theme;
 
 const KEY_SIZE: f32 = 75.0;
+const KEY_MARGIN: f32 = 5.0;
 const KEY_TEXT_SIZE: f32 = 16.0;
+const ROW_PADDING: f32 = 2.0;
+/// How many keys wide the enter and backspace keys are.
+const WIDE_KEY: f32 = 1.5;
+/// Pressed keys are darkened by this much.
+const PRESSED_SHADE: f32 = 0.75;
+const KEY_MARKER_SIZE: f32 = 12.0;
 
 pub struct KeyboardPlugin;
 
@@ -12,48 +25,178 @@ impl Plugin for KeyboardPlugin {
 	fn build(&self, app: &mut App) {
 		app
 			.add_startup_system(setup_keyboard.label(SysLabel::Setup))
+			.add_system(read_keyboard.before(SysLabel::Input))
 			.add_system(simulate_keyboard.before(SysLabel::Input))
 		;
 	}
 }
 
+/// The built in keyboard layouts.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
+#[derive(ValueEnum)]
+pub enum Layout {
+	Qwerty,
+	Azerty,
+	Qwertz,
+	Dvorak,
+	Colemak,
+}
+
+/// The rows of letters on the on-screen keyboard.
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct KeyboardLayout {
+	pub rows: Vec<String>,
+}
+
+impl KeyboardLayout {
+	/// Gets a built in layout.
+	pub fn built_in(layout: Layout) -> Self {
+		let raw = match layout {
+			Layout::Qwerty => include_str!("../assets/layouts/qwerty.layout"),
+			Layout::Azerty => include_str!("../assets/layouts/azerty.layout"),
+			Layout::Qwertz => include_str!("../assets/layouts/qwertz.layout"),
+			Layout::Dvorak => include_str!("../assets/layouts/dvorak.layout"),
+			Layout::Colemak => include_str!("../assets/layouts/colemak.layout"),
+		};
+		
+		KeyboardLayout::parse(raw)
+	}
+	
+	/// Parses a layout file, which has one row of keys per line.
+	/// Blank lines and lines starting with `#` are skipped.
+	pub fn parse(raw: &str) -> Self {
+		let rows = raw.lines()
+			.map(|line| line.trim())
+			.filter(|line| !line.is_empty() && !line.starts_with('#'))
+			.map(|line| line.to_owned())
+			.collect();
+		
+		KeyboardLayout { rows }
+	}
+	
+	/// Fits the layout to the alphabet of a [`LanguagePack`].
+	/// Keys that are not in the alphabet are removed, and letters without a key are added in an extra row.
+	pub fn for_language(mut self, pack: &LanguagePack) -> Self {
+		for row in self.rows.iter_mut() {
+			row.retain(|c| pack.is_letter(lower(c)));
+		}
+		
+		let missing: String = pack.alphabet.iter()
+			.filter(|c| !self.rows.iter().any(|row| row.chars().any(|k| lower(k) == **c)))
+			.map(|c| upper(*c))
+			.collect();
+		if !missing.is_empty() {
+			self.rows.push(missing);
+		}
+		
+		self.rows.retain(|row| !row.is_empty());
+		self
+	}
+}
+
 #[derive(Component)]
 pub struct Key {
-	pub key: char,
+	/// What pressing the key does.
+	pub action: InputAction,
 	pub old: Interaction,
 	pub tt: TileType,
 }
 
 impl Key {
-	pub fn new(key: char) -> Self {
-		Key { key, old: Interaction::None, tt: TileType::Default }
+	pub fn new(action: InputAction) -> Self {
+		Key { action, old: Interaction::None, tt: TileType::Default }
+	}
+	
+	/// The lowercase letter of this key, if it types one.
+	pub fn letter(&self) -> Option<char> {
+		match self.action {
+			InputAction::Letter(c) => Some(c),
+			_ => None,
+		}
+	}
+}
+
+/// Turns presses of the physical keyboard into [`InputAction`]s.
+///
+/// Letters are read from the typed characters rather than the key codes, so that the OS keyboard layout is respected
+/// and letters outside of A-Z can be typed.
+fn read_keyboard(
+	keys: Res<Input<KeyCode>>,
+	mut received_char_r: EventReader<ReceivedCharacter>,
+	pack: Res<LanguagePack>,
+	mut input_w: EventWriter<InputAction>,
+) {
+	if keys.just_pressed(KeyCode::Back) {
+		input_w.send(InputAction::Backspace);
+	}
+	if keys.just_pressed(KeyCode::Return) || keys.just_pressed(KeyCode::NumpadEnter) {
+		input_w.send(InputAction::Submit);
+	}
+	
+	for received_char in received_char_r.iter() {
+		for c in pack.fold(received_char.char).chars() {
+			if pack.is_letter(c) {
+				input_w.send(InputAction::Letter(c));
+			}
+		}
 	}
 }
 
+/// Turns clicks and taps on the on-screen keyboard into [`InputAction`]s.
+/// Keys are darkened while they are held down.
 fn simulate_keyboard(
-	mut key_q: Query<(&Interaction, &mut Key), (Changed<Interaction>, With<Button>)>,
-	mut keys: ResMut<Input<KeyCode>>,
+	mut key_q: Query<(&Interaction, &mut Key, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>,
+	mut input_w: EventWriter<InputAction>,
+	theme: Res<Theme>,
 ) {
-	for (interaction, key) in key_q.iter_mut() {
+	for (interaction, key, color) in key_q.iter_mut() {
 		let interaction: Interaction = *interaction;
 		let mut key: Mut<Key> = key;
-
+		let mut color: Mut<BackgroundColor> = color;
+		
 		// Change detection is conservative. There is no guarantee that the value actually changed.
 		// Therefore, we should keep track of the old value and make sure it really changed.
 		if interaction == Clicked && key.old != Clicked {
-			keys.press(key.key.get_keycode().unwrap())
-		}
-		else if interaction != Clicked && key.old == Clicked {
-			keys.release(key.key.get_keycode().unwrap())
+			input_w.send(key.action);
 		}
 		
+		let base = theme.tile_color(key.tt);
+		color.0 = if interaction == Clicked {
+			Color::rgba(base.r() * PRESSED_SHADE, base.g() * PRESSED_SHADE, base.b() * PRESSED_SHADE, base.a())
+		} else {
+			base
+		};
+		
 		key.old = interaction;
 	}
 }
 
+/// Gets the size of the on-screen keyboard, before the [`UiScale`] is applied.
+pub fn keyboard_size(layout: &KeyboardLayout) -> Vec2 {
+	let key_total = KEY_SIZE + KEY_MARGIN * 2.0;
+	let longest = layout.rows.iter()
+		.enumerate()
+		.map(|(idx, row)| {
+			let wide_keys = if idx + 1 == layout.rows.len() { 2.0 * WIDE_KEY } else { 0.0 };
+			row.chars().count() as f32 + wide_keys
+		})
+		.fold(0.0, f32::max);
+	
+	Vec2::new(
+		longest * key_total + ROW_PADDING * 2.0,
+		layout.rows.len() as f32 * (key_total + ROW_PADDING * 2.0),
+	)
+}
+
 fn setup_keyboard(
 	mut commands: Commands,
 	asset_server: Res<AssetServer>,
+	layout: Res<KeyboardLayout>,
+	loc: Res<Localization>,
+	theme: Res<Theme>,
 ) {
 	let texture = asset_server.load("tiles/key_tile.png");
 	let font = asset_server.load("fonts/Swansea.ttf");
@@ -63,7 +206,7 @@ fn setup_keyboard(
 			background_color: Color::NONE.into(),
 			style: Style {
 				align_self: AlignSelf::FlexEnd,
-				size: Size::new(Val::Percent(100.0), Val::Percent(24.0)),
+				size: Size::new(Val::Percent(100.0), Val::Px(keyboard_size(&layout).y)),
 				justify_content: JustifyContent::Center,
 				flex_direction: FlexDirection::Column,
 				..Default::default()
@@ -73,19 +216,31 @@ fn setup_keyboard(
 		.insert(Name::new("Keyboard"))
 		.id();
 	
-	spawn_row("QWERTYUIOP", texture.clone(), font.clone(), &mut commands, keyboard);
-	spawn_row("ASDFGHJKL",  texture.clone(), font.clone(), &mut commands, keyboard);
-	spawn_row("ZXCVBNM",    texture.clone(), font.clone(), &mut commands, keyboard);
-	// TODO: add enter and backspace
+	for (idx, row) in layout.rows.iter().enumerate() {
+		let mut keys: Vec<_> = row.chars()
+			.map(|c| (InputAction::Letter(lower(c)), c.to_string()))
+			.collect();
+		
+		// Enter and backspace go on either side of the last row.
+		if idx + 1 == layout.rows.len() {
+			keys.insert(0, (InputAction::Submit, loc.get("key-enter").to_owned()));
+			keys.push((InputAction::Backspace, loc.get("key-backspace").to_owned()));
+		}
+		
+		spawn_row(&keys, texture.clone(), font.clone(), *theme, &mut commands, keyboard);
+	}
 }
 
 fn spawn_row(
-	letters: &str,
+	keys: &[(InputAction, String)],
 	texture: Handle<Image>,
 	font: Handle<Font>,
+	theme: Theme,
 	commands: &mut Commands,
 	keyboard: Entity,
 ) {
+	let name: String = keys.iter().map(|(_, label)| label.as_str()).collect();
+	
 	// Keyboard
 	commands.entity(keyboard)
 		.with_children(|keyboard_cb| {
@@ -93,34 +248,37 @@ fn spawn_row(
 				.spawn(NodeBundle {
 					background_color: Color::NONE.into(),
 					style: Style {
-						padding: UiRect::all(Val::Px(2.0)),
+						padding: UiRect::all(Val::Px(ROW_PADDING)),
 						justify_content: JustifyContent::Center,
 						flex_direction: FlexDirection::Row,
 						..Default::default()
 					},
 					..Default::default()
 				})
-				.insert(Name::new(format!("{} row", letters)))
+				.insert(Name::new(format!("{} row", name)))
 				// Spawn each key
 				.with_children(|row_cb| {
-					for letter in letters.chars() {
+					for (action, label) in keys {
+						let width = match action {
+							InputAction::Letter(_) => KEY_SIZE,
+							_ => KEY_SIZE * WIDE_KEY,
+						};
+						
 						row_cb.spawn(ButtonBundle {
 							image: UiImage(texture.clone()),
-							// node: Node{size: Vec2::new(KEY_SIZE, KEY_SIZE)},
-							background_color: BackgroundColor(*L_GREY),
+							background_color: BackgroundColor(theme.tile_color(TileType::Default)),
 							style: Style {
-								margin: UiRect::all(Val::Px(5.0)),
+								margin: UiRect::all(Val::Px(KEY_MARGIN)),
 								align_items: AlignItems::Center,
-								size: Size::new(Val::Px(KEY_SIZE), Val::Px(KEY_SIZE)),
-								// size: Size::new(Val::Auto, Val::Auto),
+								size: Size::new(Val::Px(width), Val::Px(KEY_SIZE)),
 								flex_grow: 0.0,
 								flex_shrink: 1.0,
 								..Default::default()
 							},
 							..Default::default()
 						})
-						.insert(Key::new(letter))
-						.insert(Name::new(format!("{} key", letter)))
+						.insert(Key::new(*action))
+						.insert(Name::new(format!("{} key", label)))
 						.with_children(|key_cb| {
 							// Text component
 							key_cb.spawn(TextBundle {
@@ -130,15 +288,14 @@ fn spawn_row(
 										bottom: Val::Px(0.27*KEY_TEXT_SIZE),
 										..Default::default()
 									},
-									// padding: Rect::all(Val::Px(32.0)),
 									..Default::default()
 								},
 								text: Text::from_section(
-									letter,
+									label.clone(),
 									TextStyle {
 										font: font.clone(),
 										font_size: KEY_TEXT_SIZE,
-										color: Color::WHITE,
+										color: theme.letter_color(TileType::Default),
 									}
 								).with_alignment(TextAlignment {
 									horizontal: HorizontalAlign::Center,
@@ -147,6 +304,23 @@ fn spawn_row(
 								
 								..Default::default()
 							});
+							// Marker in the top right corner
+							key_cb
+								.spawn(ImageBundle {
+									style: Style {
+										position_type: PositionType::Absolute,
+										position: UiRect {
+											top: Val::Px(KEY_MARGIN),
+											right: Val::Px(KEY_MARGIN),
+											..Default::default()
+										},
+										size: Size::new(Val::Px(KEY_MARKER_SIZE), Val::Px(KEY_MARKER_SIZE)),
+										..Default::default()
+									},
+									visibility: Visibility { is_visible: false },
+									..Default::default()
+								})
+								.insert(Marker);
 						})
 						;
 					}
@@ -155,3 +329,16 @@ fn spawn_row(
 		})
 	;
 }
+
+#[test]
+fn test_layout_for_language() {
+	use wordle::language::Language;
+	
+	let de = LanguagePack::built_in(Language::De);
+	let layout = KeyboardLayout::built_in(Layout::Dvorak).for_language(&de);
+	assert_eq!(layout.rows, ["PYFGCRL", "AOEUIDHTNS", "QJKXBMWVZ", "ÄÖÜ"]);
+	
+	let en = LanguagePack::built_in(Language::En);
+	let layout = KeyboardLayout::parse(&de.keyboard.join("\n")).for_language(&en);
+	assert_eq!(layout.rows, ["QWERTZUIOP", "ASDFGHJKL", "YXCVBNM"]);
+}
@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+use clap::ValueEnum;
+use crate::util::lower;
+
+/// The built in languages.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug, Default)]
+#[derive(ValueEnum)]
+pub enum Language {
+	#[default]
+	En,
+	Es,
+	De,
+	Fr,
+}
+
+/// A language pack: the alphabet, on-screen keyboard and word list of a language.
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct LanguagePack {
+	/// The name of the language, in that language.
+	pub name: String,
+	/// The lowercase letters that words are made of.
+	pub alphabet: Vec<char>,
+	/// Letters that are replaced when typed or read from a word list, like `é` to `e`.
+	pub folds: Vec<(char, String)>,
+	/// The rows of the usual on-screen keyboard, used when no other layout is picked.
+	pub keyboard: Vec<String>,
+	/// The built in word list.
+	pub words: &'static str,
+}
+
+impl LanguagePack {
+	/// Gets the pack of a built in language.
+	pub fn built_in(language: Language) -> Self {
+		let (pack, words) = match language {
+			Language::En => (include_str!("../assets/lang/en.pack"), include_str!("../assets/dictionary_reduced.txt")),
+			Language::Es => (include_str!("../assets/lang/es.pack"), include_str!("../assets/lang/es.txt")),
+			Language::De => (include_str!("../assets/lang/de.pack"), include_str!("../assets/lang/de.txt")),
+			Language::Fr => (include_str!("../assets/lang/fr.pack"), include_str!("../assets/lang/fr.txt")),
+		};
+
+		LanguagePack::parse(pack, words).unwrap_or_else(|err| panic!("bad built in language pack {:?}: {}", language, err))
+	}
+
+	/// Parses a language pack file.
+	///
+	/// Each line is a `key = value` pair. The keys are
+	///  - `name`: the name of the language.
+	///  - `alphabet`: the letters of the language.
+	///  - `fold`: space separated `from:to` pairs of letters to replace.
+	///  - `keyboard`: space separated rows of the on-screen keyboard.
+	pub fn parse(raw: &str, words: &'static str) -> Result<Self, String> {
+		let mut name = None;
+		let mut alphabet = None;
+		let mut folds = Vec::new();
+		let mut keyboard = None;
+
+		for (idx, line) in raw.lines().enumerate() {
+			let line = line.trim();
+			if line.is_empty() || line.starts_with('#') { continue; }
+
+			let (key, value) = line.split_once('=')
+				.ok_or_else(|| format!("line {}: expected `key = value`", idx + 1))?;
+			let value = value.trim();
+
+			match key.trim() {
+				"name" => name = Some(value.to_owned()),
+				"alphabet" => alphabet = Some(value.chars().map(lower).collect()),
+				"fold" => {
+					for pair in value.split_whitespace() {
+						let (from, to) = pair.split_once(':')
+							.ok_or_else(|| format!("line {}: expected `from:to`, got {:?}", idx + 1, pair))?;
+						let mut from_chars = from.chars();
+						match (from_chars.next(), from_chars.next()) {
+							(Some(from), None) => folds.push((from, to.to_owned())),
+							_ => return Err(format!("line {}: can only fold single letters, got {:?}", idx + 1, from)),
+						}
+					}
+				}
+				"keyboard" => keyboard = Some(value.split_whitespace().map(|row| row.to_owned()).collect()),
+				other => return Err(format!("line {}: unknown key {:?}", idx + 1, other)),
+			}
+		}
+
+		Ok(LanguagePack {
+			name: name.ok_or("missing `name`")?,
+			alphabet: alphabet.ok_or("missing `alphabet`")?,
+			folds,
+			keyboard: keyboard.ok_or("missing `keyboard`")?,
+			words,
+		})
+	}
+
+	/// Lowercases and folds a typed character into the letters it stands for.
+	pub fn fold(&self, c: char) -> String {
+		let c = lower(c);
+		match self.folds.iter().find(|(from, _)| *from == c) {
+			Some((_, to)) => to.clone(),
+			None => c.to_string(),
+		}
+	}
+
+	/// Lowercases and folds every letter of a word.
+	pub fn fold_word(&self, word: &str) -> String {
+		word.chars().map(|c| self.fold(c)).collect()
+	}
+
+	/// Returns whether the lowercase letter is part of the alphabet.
+	pub fn is_letter(&self, c: char) -> bool {
+		self.alphabet.contains(&c)
+	}
+}
+
+#[test]
+fn test_built_in_packs() {
+	use crate::dictionary::parse_word_list;
+
+	for language in Language::value_variants() {
+		let pack = LanguagePack::built_in(*language);
+
+		for c in pack.keyboard.iter().flat_map(|row| row.chars()) {
+			assert!(pack.is_letter(lower(c)), "{:?} keyboard has {:?}, which is not in the alphabet", language, c);
+		}
+
+		let (words, malformed) = parse_word_list(pack.words, 5, &pack);
+		assert!(!words.is_empty());
+		assert_eq!(malformed, [], "{:?} word list has malformed entries", language);
+	}
+}
+
+#[test]
+fn test_fold() {
+	let de = LanguagePack::built_in(Language::De);
+	assert_eq!(de.fold_word("Spaß"), "spass");
+	assert_eq!(de.fold_word("Küche"), "küche");
+
+	let fr = LanguagePack::built_in(Language::Fr);
+	assert_eq!(fr.fold_word("École"), "ecole");
+}
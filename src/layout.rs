@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use bevy::window::WindowResized;
+use crate::{TILE_MARGIN, TILE_TOTAL};
+use crate::components::GameSettings;
+use crate::keyboard::{keyboard_size, KeyboardLayout};
+
+pub struct LayoutPlugin;
+
+impl Plugin for LayoutPlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.insert_resource(UiScale { scale: 1.0 })
+			.add_startup_system(setup_layout)
+			.add_system(resize_layout)
+		;
+	}
+}
+
+/// Where things are on the screen.
+///
+/// The board, with the title above it, and the keyboard under it are laid out in world units. The camera then scales
+/// the world so that all of it fits in the window, whatever its aspect ratio. The keyboard is UI, so the [`UiScale`]
+/// is set to match the camera.
+#[derive(Clone, Debug)]
+#[derive(PartialEq)]
+#[derive(Resource)]
+pub struct ScreenLayout {
+	/// The area that always has to be visible, in world units.
+	pub content: Vec2,
+	/// The height of the keyboard, in world units.
+	pub keyboard_height: f32,
+	/// The area that is visible, in world units.
+	pub view: Vec2,
+	/// How many logical pixels there are per world unit.
+	pub scale: f32,
+}
+
+impl ScreenLayout {
+	/// Lays out a board and keyboard. The window is assumed to fit the content exactly, until it is resized.
+	pub fn new(settings: &GameSettings, keyboard: &KeyboardLayout) -> Self {
+		let keyboard = keyboard_size(keyboard);
+		// The title takes up a row, and the banner needs room for a few letters.
+		let board = Vec2::new(
+			settings.word_length.max(4) as f32 * TILE_TOTAL,
+			(settings.guesses + 1) as f32 * TILE_TOTAL,
+		);
+		
+		let content = Vec2::new(board.x.max(keyboard.x), board.y + keyboard.y) + TILE_MARGIN * 2.0;
+		
+		ScreenLayout {
+			content,
+			keyboard_height: keyboard.y,
+			view: content,
+			scale: 1.0,
+		}
+	}
+	
+	/// Fits the content to a window size, in logical pixels.
+	pub fn resize(&mut self, width: f32, height: f32) {
+		let (width, height) = (width.max(1.0), height.max(1.0));
+		self.scale = (width / self.content.x).min(height / self.content.y);
+		self.view = Vec2::new(width, height) / self.scale;
+	}
+	
+	/// The height of the camera.
+	/// The board is centered on the origin, so the camera is moved down to make room for the keyboard.
+	pub fn camera_y(&self) -> f32 {
+		-self.keyboard_height / 2.0
+	}
+	
+	/// The height of the bottom edge of the view.
+	pub fn bottom(&self) -> f32 {
+		self.camera_y() - self.view.y / 2.0
+	}
+	
+	/// Converts a touch position, in logical pixels from the top left of the window, to one from the bottom left.
+	/// The mouse cursor, and [`ScreenLayout::to_world`], measure from the bottom left.
+	pub fn touch_to_window(&self, position: Vec2) -> Vec2 {
+		Vec2::new(position.x, self.view.y * self.scale - position.y)
+	}
+	
+	/// Converts a position in the window, in logical pixels from the bottom left, to world units.
+	pub fn to_world(&self, position: Vec2) -> Vec2 {
+		Vec2::new(
+			position.x / self.scale - self.view.x / 2.0,
+			position.y / self.scale + self.bottom(),
+		)
+	}
+}
+
+fn setup_layout(
+	windows: Res<Windows>,
+	mut layout: ResMut<ScreenLayout>,
+	mut ui_scale: ResMut<UiScale>,
+) {
+	if let Some(window) = windows.get_primary() {
+		layout.resize(window.width(), window.height());
+		ui_scale.scale = layout.scale as f64;
+	}
+}
+
+fn resize_layout(
+	mut e_window_resize: EventReader<WindowResized>,
+	mut layout: ResMut<ScreenLayout>,
+	mut ui_scale: ResMut<UiScale>,
+) {
+	for resize in e_window_resize.iter() {
+		layout.resize(resize.width, resize.height);
+		ui_scale.scale = layout.scale as f64;
+	}
+}
+
+#[test]
+fn test_screen_layout() {
+	use crate::keyboard::Layout;
+	
+	let settings = GameSettings::default();
+	let mut layout = ScreenLayout::new(&settings, &KeyboardLayout::built_in(Layout::Qwerty));
+	
+	// A wide window is limited by its height.
+	layout.resize(3440.0, 1440.0);
+	assert!((layout.view.y - layout.content.y).abs() < 0.01);
+	assert!(layout.view.x > layout.content.x);
+	
+	// A phone in portrait is limited by its width.
+	layout.resize(390.0, 844.0);
+	assert!((layout.view.x - layout.content.x).abs() < 0.01);
+	assert!(layout.view.y > layout.content.y);
+	assert!(layout.scale < 1.0);
+	
+	// Touches are flipped to measure from the bottom, like the cursor.
+	assert!((layout.touch_to_window(Vec2::new(10.0, 0.0)) - Vec2::new(10.0, 844.0)).length() < 0.01);
+	assert!((layout.touch_to_window(Vec2::new(10.0, 800.0)) - Vec2::new(10.0, 44.0)).length() < 0.01);
+}
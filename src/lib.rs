@@ -0,0 +1,7 @@
+//! The parts of the game that the `wordle-server` binary shares with it: word lists, language packs and the race
+//! protocol. None of these depend on the rest of the game.
+
+pub mod dictionary;
+pub mod language;
+pub mod protocol;
+pub mod util;
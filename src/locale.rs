@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use bevy::prelude::*;
+use wordle::language::Language;
+
+/// The user-facing strings of the UI, in the UI language.
+///
+/// Strings are looked up by key. Keys that are missing fall back to English, and then to the key itself.
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct Localization {
+	strings: HashMap<String, String>,
+	fallback: HashMap<String, String>,
+}
+
+impl Localization {
+	/// Gets the strings of a built in language.
+	pub fn built_in(language: Language) -> Self {
+		Localization {
+			strings: parse_strings(built_in_raw(language)),
+			fallback: parse_strings(built_in_raw(Language::En)),
+		}
+	}
+	
+	/// Gets the string for a key.
+	pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+		self.strings.get(key)
+			.or_else(|| self.fallback.get(key))
+			.map(|s| s.as_str())
+			.unwrap_or(key)
+	}
+	
+	/// Gets the string for a key, with each `{name}` replaced by the matching argument.
+	pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+		let mut string = self.get(key).to_owned();
+		for (name, value) in args {
+			string = string.replace(&format!("{{{}}}", name), &value.to_string());
+		}
+		string
+	}
+}
+
+/// Gets the strings file of a built in language.
+fn built_in_raw(language: Language) -> &'static str {
+	match language {
+		Language::En => include_str!("../assets/locale/en.txt"),
+		Language::Es => include_str!("../assets/locale/es.txt"),
+		Language::De => include_str!("../assets/locale/de.txt"),
+		Language::Fr => include_str!("../assets/locale/fr.txt"),
+	}
+}
+
+/// Parses a strings file, which has a `key = value` pair on each line.
+/// Blank lines and lines starting with `#` are skipped.
+fn parse_strings(raw: &str) -> HashMap<String, String> {
+	raw.lines()
+		.map(|line| line.trim())
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+		.filter_map(|line| line.split_once('='))
+		.map(|(key, value)| (key.trim().to_owned(), value.trim().to_owned()))
+		.collect()
+}
+
+#[test]
+fn test_built_in_strings() {
+	use clap::ValueEnum;
+	
+	let en = parse_strings(built_in_raw(Language::En));
+	for language in Language::value_variants() {
+		let strings = parse_strings(built_in_raw(*language));
+		for key in en.keys() {
+			assert!(strings.contains_key(key), "{:?} is missing {:?}", language, key);
+		}
+	}
+	
+	let loc = Localization::built_in(Language::En);
+	assert_eq!(loc.format("invalid-must-contain", &[("letter", &'E')]), "Guess must contain E");
+	assert_eq!(loc.get("missing-key"), "missing-key");
+}
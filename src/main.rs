@@ -1,22 +1,69 @@
-mod util;
+mod accessibility;
 mod anim;
-mod keyboard;
-mod events;
+mod banner;
+mod bot;
 mod components;
+mod endless;
+mod events;
+mod evil;
+mod keyboard;
+mod layout;
+mod locale;
+mod markers;
+#[cfg(not(target_family = "wasm"))]
+mod net;
+mod options;
+mod particles;
+mod reverse;
+mod sound;
+#[cfg(not(target_family = "wasm"))]
+mod spectator;
+mod stats;
+mod theme;
+mod timer;
+mod toast;
+mod touch;
+mod tween;
+mod versus;
 
 use bevy::prelude::*;
 use bevy::render::camera::ScalingMode;
-use bevy::window::WindowResized;
 use bevy_editor_pls::EditorPlugin;
 use lazy_static::lazy_static;
 use rand::prelude::SliceRandom;
-use rand::thread_rng;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use wordle::dictionary::load_dictionary;
+use wordle::language::LanguagePack;
+use wordle::util::upper;
 use components::*;
-use crate::anim::AnimPlugin;
-use crate::events::{Events, GameWin, Guess, InvalidGuess, TypedLetter};
-use crate::keyboard::KeyboardPlugin;
+use crate::accessibility::AccessibilityPlugin;
+use crate::anim::{AnimPlugin, SquashLens};
+use crate::banner::{Banner, BannerPlugin};
+use crate::bot::BotPlugin;
+use crate::endless::EndlessPlugin;
+use crate::evil::Candidates;
+use crate::events::{Events, GameLoss, GameWin, Guess, InputAction, InvalidGuess, InvalidReason, NewGame, TypedLetter};
+use crate::keyboard::{Key, KeyboardPlugin};
+use crate::layout::{LayoutPlugin, ScreenLayout};
+use crate::locale::Localization;
+use crate::markers::MarkerPlugin;
+#[cfg(not(target_family = "wasm"))]
+use crate::net::{Connection, NetPlugin};
+use crate::options::Options;
+use crate::particles::ParticlePlugin;
+use crate::reverse::{ReversePlugin, Targets};
+use crate::sound::SoundPlugin;
+#[cfg(not(target_family = "wasm"))]
+use crate::spectator::SpectatorPlugin;
+use crate::stats::Stats;
+use crate::theme::Theme;
+use crate::timer::TimerPlugin;
+use crate::toast::ToastPlugin;
+use crate::touch::TouchPlugin;
+use crate::tween::Tween;
 use crate::TileType::Correct;
-use crate::util::GetChar;
+use crate::versus::VersusPlugin;
 
 const TILE_SIZE: f32 = 100.0;
 const TILE_MARGIN: f32 = 10.0;
@@ -30,81 +77,140 @@ lazy_static! {
 }
 
 const TEXT_SIZE: f32 = 30.0;
+const MARKER_SIZE: f32 = 18.0;
 
 fn main() {
+	let options = Options::from_env();
+	let pack = LanguagePack::built_in(options.language);
+	let mut settings = options.settings();
+	let mode = settings.mode;
+	
+	// The server picks the game, so join it before anything is set up.
+	#[cfg(not(target_family = "wasm"))]
+	let connection = options.server.as_ref().map(|addr| {
+		if options.mode != GameMode::Classic {
+			Options::error("races can only be played in classic mode");
+		}
+		let connection = Connection::connect(addr, options.language, &options.name)
+			.unwrap_or_else(|e| Options::error(format!("could not join the race at {}: {}", addr, e)));
+		connection.apply(&mut settings);
+		connection
+	});
+	let keyboard_layout = options.keyboard_layout(&pack);
+	
+	let dic = WordDic(load_dictionary(settings.dictionary.as_deref(), settings.word_length, &pack));
+	if dic.is_empty() {
+		Options::error(format!("the dictionary has no {} letter words", settings.word_length));
+	}
 
 	let mut app = App::new();
 
 	app
-		.insert_resource(ClearColor(Color::rgb_u8(0x12, 0x12, 0x13)))
-		.add_plugins(DefaultPlugins);
+		.insert_resource(ClearColor(options.theme.background()))
+		.insert_resource(options.theme)
+		.insert_resource(ScreenLayout::new(&settings, &keyboard_layout))
+		.insert_resource(settings)
+		.insert_resource(dic)
+		.insert_resource(options.anim_settings())
+		.insert_resource(options.audio_settings())
+		.insert_resource(options.display_settings())
+		.insert_resource(options.bot_settings())
+		.insert_resource(Stats::load())
+		.insert_resource(options.confetti_settings())
+		.insert_resource(keyboard_layout)
+		.insert_resource(Localization::built_in(options.ui_language.unwrap_or(options.language)))
+		.insert_resource(pack)
+		.add_plugins(DefaultPlugins.set(WindowPlugin {
+			window: WindowDescriptor {
+				title: "Wordle".to_owned(),
+				mode: options.window_mode(),
+				resizable: options.resizable(),
+				..Default::default()
+			},
+			..Default::default()
+		}));
 
 	#[cfg(target_family = "wasm")]
 	app.add_plugin(bevy_web_fullscreen::FullViewportPlugin);
 
 	app
 		.insert_resource(Pause::new())
-		.insert_resource(UiScale { scale: 1.0 })
+		.insert_resource(InputBuffer::default())
 		.add_plugin(Events)
+		.add_plugin(LayoutPlugin)
 		.add_plugin(AnimPlugin)
 		.add_plugin(KeyboardPlugin)
+		.add_plugin(TouchPlugin)
+		.add_plugin(ToastPlugin)
+		.add_plugin(SoundPlugin)
+		.add_plugin(ParticlePlugin)
+		.add_plugin(BannerPlugin)
+		.add_plugin(AccessibilityPlugin)
+		.add_plugin(MarkerPlugin)
+		.add_plugin(TimerPlugin)
 		// .add_plugin(EditorPlugin)
 
 		.add_startup_system(setup.label(SysLabel::Setup))
 		
-		// LOGIC SET
-		.add_system_set(SystemSet::new()
-			.label(SysLabel::Logic)
-			.after(SysLabel::Input)
-			
-			.with_system(update_chars)
-		)
-		
-		.add_system(update_ui_scale)
-		.add_system(get_input.label(SysLabel::Input).with_run_criteria(not_paused))
-		.add_system(update_tile_chars.label(SysLabel::Graphics))
-
-		.run();
+		.add_system(get_input.label(SysLabel::Input))
+		.add_system(new_game.before(SysLabel::Input))
+		.add_system(update_tile_chars.label(SysLabel::Graphics));
+	
+	match mode {
+		GameMode::Versus => { app.add_plugin(VersusPlugin); }
+		GameMode::Endless => { app.add_plugin(EndlessPlugin); }
+		GameMode::Reverse => { app.add_plugin(ReversePlugin); }
+		GameMode::Bot => { app.add_plugin(BotPlugin); }
+		_ => {}
+	}
+	
+	#[cfg(not(target_family = "wasm"))]
+	if let Some(connection) = connection {
+		app
+			.insert_resource(connection)
+			.add_plugin(NetPlugin)
+			.add_plugin(SpectatorPlugin);
+	}
+	
+	app.run();
 }
 
-// TODO: change camera to scale
-
 // TODO: MILESTONES
 //      game win events/anim
-//      Show Word on fail
 // 		restart button
 
-fn update_ui_scale(
-	mut e_window_resize: EventReader<WindowResized>,
-	mut ui_scale: ResMut<UiScale>,
-) {
-	for resize in e_window_resize.iter() {
-		ui_scale.scale = (resize.height / 1080.0) as f64;
-	}
-}
-
 fn setup(
 	mut commands: Commands,
 	asset_server: Res<AssetServer>,
+	settings: Res<GameSettings>,
+	dic: Res<WordDic>,
+	loc: Res<Localization>,
+	theme: Res<Theme>,
+	layout: Res<ScreenLayout>,
 ) {
+	// Always show all of the content, whatever the shape of the window.
 	let mut camera = Camera2dBundle::default();
-	camera.projection.scaling_mode = ScalingMode::FixedVertical(1080.0);
-	// camera.projection.scaling_mode = ScalingMode::Auto { min_width: 1920.0, min_height: 1080.0 };
-	// camera.transform.translation.y = -20.0;
+	camera.projection.scaling_mode = ScalingMode::Auto {
+		min_width: layout.content.x,
+		min_height: layout.content.y,
+	};
+	camera.transform.translation.y = layout.camera_y();
 	commands.spawn(camera);
 	
 	commands.insert_resource(Cursor {x: 0, y: 0});
+	commands.insert_resource(GameOver::default());
 	
-	// Dictionary
-	let dic_raw = include_str!("../assets/dictionary_reduced.txt");
-	let dic: Vec<String> = dic_raw.trim().lines().map(|w| w.to_owned()).collect();
-	println!("word count: {}", dic.len());
-	
-	let mut rng = thread_rng();
+	let mut rng = StdRng::seed_from_u64(settings.seed);
 	let correct_word = dic.choose(&mut rng).unwrap().to_owned();
 	// println!("Word is: {}", correct_word);
+	if settings.mode == GameMode::Reverse {
+		commands.insert_resource(Targets::new(&correct_word, &dic, settings.guesses, &mut rng));
+	}
 	commands.insert_resource(Word(correct_word));
-	commands.insert_resource(WordDic(dic));
+	commands.insert_resource(AnswerRng(rng));
+	if settings.mode == GameMode::Evil {
+		commands.insert_resource(Candidates(dic.0.clone()));
+	}
 	
 	let tile_assets = TileAssets {
 		default: asset_server.load("tiles/outline.png"),
@@ -118,7 +224,7 @@ fn setup(
 	let title_style = TextStyle {
 		font: font.clone(),
 		font_size: TILE_SIZE/2.0,
-		color: Color::WHITE,
+		color: theme.text(),
 	};
 	let alignment = TextAlignment {
 		vertical: VerticalAlign::Center,
@@ -127,56 +233,58 @@ fn setup(
 	
 	commands
 		.spawn(Text2dBundle {
-			text: Text::from_section("WORDLE", title_style).with_alignment(alignment),
-			transform: Transform::from_translation(Vec3::new(0.0, TILE_TOTAL * 4.0 + TILE_MARGIN, 0.0)),
+			text: Text::from_section(loc.get("title"), title_style).with_alignment(alignment),
+			transform: Transform::from_translation(Vec3::new(0.0, title_height(&settings), 0.0)),
 			..Default::default()
-		});
-	
-	// create a tile map of all "null"s
-	let mut tile_map = TileMap::null();
+		})
+		.insert(Title);
 	
-	for y in 0..6 {
-		for x in 0..5 {
-			tile_map[y as usize][x as usize] =
-			spawn_tile(&mut commands, tile_assets.default.clone(), font.clone(), x, y);
-		}
-	}
+	let tiles: Vec<Vec<Entity>> = (0..settings.guesses)
+		.map(|y| (0..settings.word_length)
+			.map(|x| spawn_tile(&mut commands, tile_assets.default.clone(), font.clone(), x, y, &settings, *theme))
+			.collect()
+		)
+		.collect();
 	
-	commands.insert_resource(tile_map);
+	commands.insert_resource(TileMap::new(tiles));
 }
 
 
-fn update_chars(
-	mut tiles_q: Query<&mut Tile>,
-	mut typed_letter_r: EventReader<TypedLetter>,
-	tile_map: Res<TileMap>,
-) {
-	for typed_letter in typed_letter_r.iter() {
-		let typed_letter: &TypedLetter = typed_letter;
-		
-		if typed_letter.valid {
-			let tile_e = tile_map[typed_letter.y][typed_letter.x];
-			let mut tile = tiles_q.get_mut(tile_e).unwrap();
-			tile.c = Some(typed_letter.letter);
-		}
-	}
-}
-
+/// Applies the player's input to the board.
+///
+/// Input that arrives while the game is paused, like during the flip animation, is buffered and applied once the
+/// game resumes. Only one guess is submitted per frame, so that the next row waits for its own animation.
 fn get_input(
 	mut tiles_q: Query<&mut Tile>,
-	keys: Res<Input<KeyCode>>,
+	mut input_r: EventReader<InputAction>,
+	mut input_buffer: ResMut<InputBuffer>,
+	pause: Res<Pause>,
 	tile_map: Res<TileMap>,
 	mut cursor: ResMut<Cursor>,
+	mut game_over: ResMut<GameOver>,
 	dic: Res<WordDic>,
-	word: Res<Word>,
+	mut word: ResMut<Word>,
+	// The modes that change how guesses are judged.
+	(mut candidates, targets, mut remote): (Option<ResMut<Candidates>>, Option<Res<Targets>>, Option<ResMut<RemoteScoring>>),
+	settings: Res<GameSettings>,
 	
 	mut inv_guess_w: EventWriter<InvalidGuess>,
 	mut guess_w: EventWriter<Guess>,
 	mut typed_letter_w: EventWriter<TypedLetter>,
 	mut game_win_w: EventWriter<GameWin>,
+	mut game_loss_w: EventWriter<GameLoss>,
 ) {
-	for k in keys.get_just_pressed() {
-		if *k == KeyCode::Back {
+	// In bot mode, the bot types straight into the buffer instead.
+	input_buffer.extend(input_r.iter().copied().filter(|_| settings.mode != GameMode::Bot));
+	if pause.paused() { return; }
+	
+	while let Some(input) = input_buffer.pop_front() {
+		if game_over.0 {
+			input_buffer.clear();
+			return;
+		}
+		
+		if input == InputAction::Backspace {
 			if cursor.x > 0 {
 				cursor.x -= 1;
 			}
@@ -186,75 +294,222 @@ fn get_input(
 			tile.c = None;
 		}
 		
-		if *k == KeyCode::Return {
-			if cursor.x == 5 {
-				// Compile the guess into a string.
-				let guess: String = tile_map[cursor.y].iter()
-					.map(|e| tiles_q.get(*e).unwrap().c.unwrap())
-					.collect::<String>()
-					.to_lowercase();
-				
-				// Check Dictionary
-				if dic.binary_search(&guess).is_ok() {
-					// Check Correctness
-					let tile_iter = tile_map[cursor.y].iter();
-					
-					let correctness = correctness(&*word, &*guess);
-					for (e, c) in tile_iter.zip(correctness) {
-						let mut tile = tiles_q.get_mut(*e).unwrap();
-						tile.tt = c;
-					}
-					
-					// send event.
-					guess_w.send(Guess {
-						word: guess.clone(),
+		if input == InputAction::Submit {
+			// Compile the guess into a string.
+			let guess = row_word(&tiles_q, &tile_map, cursor.y);
+			
+			let result = validate_guess(&guess, &tiles_q, &tile_map, &cursor, &dic, &settings);
+			if let Err(reason) = result {
+				// Invalid guess; send event.
+				inv_guess_w.send(InvalidGuess {
+					row: cursor.y,
+					reason,
+				});
+				continue;
+			}
+			let guess = guess.unwrap();
+			
+			// In reverse mode, the guess has to give the colors shown for the row.
+			if let Some(targets) = &targets {
+				if correctness(&*word, &*guess) != targets.0[cursor.y] {
+					inv_guess_w.send(InvalidGuess {
 						row: cursor.y,
-						correctness,
+						reason: InvalidReason::WrongPattern,
 					});
-					
-					if correctness == [TileType::Correct; 5] {
-						// Game won
-						game_win_w.send(GameWin {
-							word: guess,
-							row: cursor.y,
-						})
-					} else {
-						// Game not won
-						if cursor.y == 5 {
-							println!("Word was: {}", *word);
+					continue;
+				}
+			}
+			
+			// Check Correctness
+			let tile_iter = tile_map[cursor.y].iter();
+			
+			// In evil mode, the answer is whichever word gives the guess the least useful feedback.
+			if let Some(candidates) = candidates.as_mut() {
+				candidates.narrow(&guess);
+				word.0 = candidates.0[0].clone();
+			}
+			
+			let correctness = match remote.as_mut() {
+				// In a race, the guess waits here until the server has scored it.
+				Some(remote) => {
+					if !remote.waiting {
+						remote.waiting = true;
+						remote.outgoing = Some(guess.clone());
+					}
+					match remote.reply.take() {
+						Some(Ok(marks)) => {
+							remote.waiting = false;
+							marks
+						}
+						Some(Err(reason)) => {
+							remote.waiting = false;
+							inv_guess_w.send(InvalidGuess {
+								row: cursor.y,
+								reason,
+							});
+							continue;
+						}
+						None => {
+							input_buffer.push_front(InputAction::Submit);
+							return;
 						}
-						cursor.next_line();
 					}
-					
-					continue;
 				}
+				None => correctness(&*word, &*guess),
+			};
+			for (e, c) in tile_iter.zip(correctness.iter()) {
+				let mut tile = tiles_q.get_mut(*e).unwrap();
+				tile.tt = *c;
 			}
 			
-			// Invalid guess; send event.
-			inv_guess_w.send(InvalidGuess {
+			let won = all_correct(&correctness);
+			
+			// send event.
+			guess_w.send(Guess {
+				word: guess.clone(),
 				row: cursor.y,
+				correctness,
 			});
 			
+			if won {
+				// Game won
+				game_win_w.send(GameWin {
+					word: guess,
+					row: cursor.y,
+				});
+				game_over.0 = true;
+			} else {
+				// Game not won. If this was the last row, the answer is revealed once it is flipped.
+				cursor.next_line();
+				if cursor.y >= settings.guesses {
+					game_loss_w.send(GameLoss);
+					game_over.0 = true;
+				}
+			}
+			
+			break;
 		}
 		
 		// if the key is a character
-		if let Some(c) = k.get_char() {
-			let valid = cursor.x < 5;
+		if let InputAction::Letter(c) = input {
+			let valid = cursor.x < settings.word_length;
 			// send event
 			typed_letter_w.send(TypedLetter {
 				x: cursor.x,
 				y: cursor.y,
 				valid,
-				letter: c,
+				letter: upper(c),
 			});
 			if valid {
-				cursor.next_char();
+				let mut tile = tiles_q.get_mut(tile_map[cursor.y][cursor.x]).unwrap();
+				tile.c = Some(upper(c));
+				cursor.next_char(settings.word_length);
 			}
 			
 		}
 	}
 }
 
+/// Picks a new word and clears the board, the keyboard and the end of game animations.
+fn new_game(
+	mut commands: Commands,
+	mut new_game_r: EventReader<NewGame>,
+	mut tiles_q: Query<(&mut Tile, &Children, &mut Handle<Image>, &mut Sprite)>,
+	mut keys_q: Query<(&mut Key, &mut BackgroundColor, &Children)>,
+	mut text_q: Query<&mut Text>,
+	mut title_q: Query<(Entity, &mut Transform), With<Title>>,
+	cleanup_q: Query<Entity, Or<(With<Banner>, With<Answer>)>>,
+	dic: Res<WordDic>,
+	mut rng: ResMut<AnswerRng>,
+	mut word: ResMut<Word>,
+	candidates: Option<ResMut<Candidates>>,
+	mut cursor: ResMut<Cursor>,
+	mut game_over: ResMut<GameOver>,
+	mut input_buffer: ResMut<InputBuffer>,
+	tile_assets: Res<TileAssets>,
+	theme: Res<Theme>,
+) {
+	// Several requests in one frame only need one new game.
+	if new_game_r.iter().count() == 0 { return; }
+	
+	word.0 = dic.choose(&mut rng.0).unwrap().to_owned();
+	if let Some(mut candidates) = candidates {
+		candidates.0 = dic.0.clone();
+	}
+	*cursor = Cursor { x: 0, y: 0 };
+	game_over.0 = false;
+	input_buffer.clear();
+	
+	for (mut tile, children, mut texture, mut sprite) in tiles_q.iter_mut() {
+		tile.c = None;
+		tile.tt = TileType::Default;
+		*texture = tile_assets.default.clone();
+		sprite.color = theme.outline();
+		if let Ok(mut text) = text_q.get_mut(children[0]) {
+			text.sections[0].style.color = theme.text();
+		}
+	}
+	
+	for (mut key, mut color, children) in keys_q.iter_mut() {
+		key.tt = TileType::Default;
+		color.0 = theme.tile_color(TileType::Default);
+		if let Ok(mut text) = text_q.get_mut(children[0]) {
+			text.sections[0].style.color = theme.letter_color(TileType::Default);
+		}
+	}
+	
+	for (entity, mut transform) in title_q.iter_mut() {
+		commands.entity(entity).remove::<Tween<SquashLens>>();
+		transform.scale = Vec3::ONE;
+	}
+	
+	for entity in cleanup_q.iter() {
+		commands.entity(entity).despawn_recursive();
+	}
+}
+
+/// Gets the lowercase word typed on a row, or [`None`] if the row is not full.
+fn row_word(
+	tiles_q: &Query<&mut Tile>,
+	tile_map: &TileMap,
+	row: usize,
+) -> Option<String> {
+	tile_map[row].iter()
+		.map(|e| tiles_q.get(*e).unwrap().c)
+		.collect::<Option<String>>()
+		.map(|w| w.to_lowercase())
+}
+
+/// Checks that a guess is allowed to be played on the cursor's row.
+fn validate_guess(
+	guess: &Option<String>,
+	tiles_q: &Query<&mut Tile>,
+	tile_map: &TileMap,
+	cursor: &Cursor,
+	dic: &WordDic,
+	settings: &GameSettings,
+) -> Result<(), InvalidReason> {
+	let guess = guess.as_ref().ok_or(InvalidReason::NotEnoughLetters)?;
+	
+	// Check Dictionary
+	if dic.binary_search(guess).is_err() {
+		return Err(InvalidReason::NotInWordList);
+	}
+	
+	// Reverse mode has its own rules for each row, which could contradict hard mode.
+	if settings.hard && settings.mode != GameMode::Reverse {
+		for row in 0..cursor.y {
+			let prev = row_word(tiles_q, tile_map, row).unwrap();
+			let prev_correctness: Vec<_> = tile_map[row].iter()
+				.map(|e| tiles_q.get(*e).unwrap().tt)
+				.collect();
+			hard_mode_check(&prev, &prev_correctness, guess)?;
+		}
+	}
+	
+	Ok(())
+}
+
 /// Updates the characters of the tiles.
 fn update_tile_chars(
 	tiles_q: Query<(&Tile, &Children), Changed<Tile>>,
@@ -281,8 +536,10 @@ fn spawn_tile(
 	commands: &mut Commands,
 	texture: Handle<Image>,
 	font: Handle<Font>,
-	x: i32,
-	y: i32,
+	x: usize,
+	y: usize,
+	settings: &GameSettings,
+	theme: Theme,
 ) -> Entity {
 	let alignment = TextAlignment {
 		vertical: VerticalAlign::Center,
@@ -292,10 +549,13 @@ fn spawn_tile(
 	let style = TextStyle {
 		font,
 		font_size: TEXT_SIZE,
-		color: Color::WHITE,
+		color: theme.text(),
 	};
 	
-	let pos = get_tile_pos(x, y);
+	let pos = get_tile_pos(x, y, settings);
+	
+	// The marker goes in the top right corner.
+	let marker_pos = TILE_SIZE / 2.0 - TILE_MARGIN - MARKER_SIZE / 2.0;
 	
 	commands.spawn_empty()
 		.with_children(|c| {
@@ -305,11 +565,23 @@ fn spawn_tile(
 					transform: Transform::from_translation(Vec3::new(0.0, TEXT_SIZE * 0.27, 1.0)),
 					..Default::default()
 				});
+			c
+				.spawn(SpriteBundle {
+					sprite: Sprite {
+						color: Color::WHITE,
+						custom_size: Some(Vec2::splat(MARKER_SIZE)),
+						..Default::default()
+					},
+					transform: Transform::from_translation(Vec3::new(marker_pos, marker_pos, 1.0)),
+					visibility: Visibility { is_visible: false },
+					..Default::default()
+				})
+				.insert(Marker);
 		})
 		.insert(SpriteBundle {
 			texture,
 			sprite: Sprite {
-				// color,
+				color: theme.outline(),
 				custom_size: Some(Vec2::splat(TILE_SIZE)),
 				..Default::default()
 			},
@@ -324,40 +596,85 @@ fn spawn_tile(
 		.id()
 }
 
-fn get_tile_pos(x: i32, y: i32) -> Vec3 {
+/// Gets the position of a tile. The board and the title above it are centered on the origin.
+/// The camera makes room for the keyboard; see [`ScreenLayout`].
+fn get_tile_pos(x: usize, y: usize, settings: &GameSettings) -> Vec3 {
+	let center_x = (settings.word_length - 1) as f32 / 2.0;
+	// The title takes up row -1.
+	let center_y = (settings.guesses as f32 - 2.0) / 2.0;
+	
 	Vec3::new(
-		(x-2) as f32 * TILE_TOTAL,
-		(3 - y) as f32 * TILE_TOTAL,
+		(x as f32 - center_x) * TILE_TOTAL,
+		(center_y - y as f32) * TILE_TOTAL,
 		0.0,
 	)
 }
 
-fn correctness(correct: &str, guess: &str) -> [TileType; 5] {
-	assert_eq!(correct.len(), 5);
-	assert_eq!(guess.len(), 5);
+/// Gets the height of the title; one tile above the top row.
+fn title_height(settings: &GameSettings) -> f32 {
+	get_tile_pos(0, 0, settings).y + TILE_TOTAL + TILE_MARGIN
+}
+
+/// Returns whether every tile of the guess is correct.
+fn all_correct(correctness: &[TileType]) -> bool {
+	correctness.iter().all(|tt| *tt == TileType::Correct)
+}
+
+/// Checks that `guess` uses every hint revealed by `prev`, as hard mode requires.
+fn hard_mode_check(prev: &str, prev_correctness: &[TileType], guess: &str) -> Result<(), InvalidReason> {
+	let guess_chars: Vec<_> = guess.chars().collect();
+	// The letters of the guess that have not been matched to a hint yet.
+	let mut unused: Vec<_> = guess_chars.iter().map(|c| Some(*c)).collect();
 	
+	// Correct letters must stay in place.
+	for (idx, (c, tt)) in prev.chars().zip(prev_correctness).enumerate() {
+		if *tt == TileType::Correct {
+			if guess_chars[idx] != c {
+				return Err(InvalidReason::MustBeAt(c, idx));
+			}
+			unused[idx] = None;
+		}
+	}
+	
+	// Close letters must be somewhere.
+	for (c, tt) in prev.chars().zip(prev_correctness) {
+		if *tt == TileType::Close {
+			match unused.iter().position(|o| *o == Some(c)) {
+				Some(idx) => unused[idx] = None,
+				None => return Err(InvalidReason::MustContain(c)),
+			}
+		}
+	}
+	
+	Ok(())
+}
+
+fn correctness(correct: &str, guess: &str) -> Vec<TileType> {
 	let guess_chars: Vec<_> = guess.chars().collect();
-	let mut correct_chars: Vec<_> = correct.chars().collect();
+	// Letters are set to `None` once matched.
+	let mut correct_chars: Vec<_> = correct.chars().map(Some).collect();
+	assert_eq!(correct_chars.len(), guess_chars.len());
+	let len = guess_chars.len();
 	
-	let mut correctness = [TileType::Wrong; 5];
+	let mut correctness = vec![TileType::Wrong; len];
 	
 	// Check correct first
-	for idx in 0..5 {
-		if guess_chars[idx] == correct_chars[idx] {
+	for idx in 0..len {
+		if Some(guess_chars[idx]) == correct_chars[idx] {
 			correctness[idx] = TileType::Correct;
-			correct_chars[idx] = '-'; // Make sure the char doesn't get matched again
+			correct_chars[idx] = None; // Make sure the char doesn't get matched again
 		}
 	}
 	
 	// Now check for wrong spot
-	for idx in 0..5 {
+	for idx in 0..len {
 		// If this character was already found to be correct, skip it.
 		if correctness[idx] == Correct { continue; }
 		
 		let c = guess_chars[idx];
-		if let Some(c_idx) = correct_chars.iter().position(|o| *o == c) {
+		if let Some(c_idx) = correct_chars.iter().position(|o| *o == Some(c)) {
 			correctness[idx] = TileType::Close;
-			correct_chars[c_idx] = '-';
+			correct_chars[c_idx] = None;
 		}
 	}
 	
@@ -372,4 +689,16 @@ fn test_correctness_logic() {
 	assert_eq!(correctness("hello", "shark"), [Wrong, Close, Wrong, Wrong, Wrong]);
 	assert_eq!(correctness("mints", "mmmmm"), [Correct, Wrong, Wrong, Wrong, Wrong]);
 	assert_eq!(correctness("slips", "ssssk"), [Correct, Close, Wrong, Wrong, Wrong]);
+	assert_eq!(correctness("cat", "act"), [Close, Close, Correct]);
+	assert_eq!(correctness("señal", "señor"), [Correct, Correct, Correct, Wrong, Wrong]);
+}
+
+#[test]
+fn test_hard_mode_check() {
+	use TileType::*;
+	let prev_correctness = correctness("hello", "lemon");
+	assert_eq!(hard_mode_check("lemon", &prev_correctness, "below"), Ok(()));
+	assert_eq!(hard_mode_check("lemon", &prev_correctness, "shark"), Err(InvalidReason::MustBeAt('e', 1)));
+	assert_eq!(hard_mode_check("lemon", &prev_correctness, "betas"), Err(InvalidReason::MustContain('l')));
+	assert_eq!(hard_mode_check("lemon", &[Wrong; 5], "shark"), Ok(()));
 }
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use crate::TileType;
+use crate::components::{DisplaySettings, Marker, Tile};
+use crate::keyboard::Key;
+
+/// Shows a shape on correct and close tiles and keys, so the game can be played without telling colours apart.
+pub struct MarkerPlugin;
+
+impl Plugin for MarkerPlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.add_startup_system(setup_markers)
+			.add_system(tile_markers)
+			.add_system(key_markers)
+		;
+	}
+}
+
+/// The textures of the markers.
+#[derive(Clone, Resource)]
+pub struct MarkerAssets {
+	pub correct: Handle<Image>,
+	pub close: Handle<Image>,
+}
+
+impl MarkerAssets {
+	/// Gets the marker for a [`TileType`], if it has one.
+	pub fn get(&self, tt: TileType) -> Option<Handle<Image>> {
+		match tt {
+			TileType::Correct => Some(self.correct.clone()),
+			TileType::Close => Some(self.close.clone()),
+			_ => None,
+		}
+	}
+}
+
+fn setup_markers(
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+) {
+	commands.insert_resource(MarkerAssets {
+		correct: asset_server.load("markers/correct.png"),
+		close: asset_server.load("markers/close.png"),
+	});
+}
+
+/// Shows the marker of a tile once it is colored.
+fn tile_markers(
+	tiles_q: Query<(&Tile, &Children), (Changed<Handle<Image>>, Without<Marker>)>,
+	mut marker_q: Query<(&mut Handle<Image>, &mut Visibility), With<Marker>>,
+	markers: Res<MarkerAssets>,
+	display_settings: Res<DisplaySettings>,
+) {
+	if !display_settings.markers { return; }
+	
+	for (tile, children) in tiles_q.iter() {
+		let tile: &Tile = tile;
+		
+		for child in children.iter() {
+			if let Ok((mut texture, mut visibility)) = marker_q.get_mut(*child) {
+				match markers.get(tile.tt) {
+					Some(marker) => {
+						*texture = marker;
+						visibility.is_visible = true;
+					}
+					None => visibility.is_visible = false,
+				}
+			}
+		}
+	}
+}
+
+/// Shows the marker of a key once it is colored.
+fn key_markers(
+	keys_q: Query<(&Key, &Children), Changed<Key>>,
+	mut marker_q: Query<(&mut UiImage, &mut Visibility), With<Marker>>,
+	markers: Res<MarkerAssets>,
+	display_settings: Res<DisplaySettings>,
+) {
+	if !display_settings.markers { return; }
+	
+	for (key, children) in keys_q.iter() {
+		let key: &Key = key;
+		
+		for child in children.iter() {
+			if let Ok((mut image, mut visibility)) = marker_q.get_mut(*child) {
+				match markers.get(key.tt) {
+					Some(marker) => {
+						image.0 = marker;
+						visibility.is_visible = true;
+					}
+					None => visibility.is_visible = false,
+				}
+			}
+		}
+	}
+}
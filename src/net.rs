@@ -0,0 +1,271 @@
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+use std::time::Duration;
+use bevy::prelude::*;
+use clap::ValueEnum;
+use wordle::language::Language;
+use wordle::protocol::{ClientMessage, Mark, Rejection, ServerMessage};
+use wordle::util::upper;
+use crate::{SysLabel, TileType};
+use crate::components::{GameMode, GameSettings, Pause, RemoteScoring, UnknownAnswer};
+use crate::events::{InvalidReason, NewGame};
+use crate::locale::Localization;
+use crate::toast::ShowToast;
+
+/// How long to wait for the server when joining.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long to show the end of a round before the next one.
+const NEXT_ROUND_DELAY: Duration = Duration::from_secs(3);
+
+/// Races other players through a server; see the `wordle-server` binary.
+pub struct NetPlugin;
+
+impl Plugin for NetPlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.init_resource::<Opponents>()
+			.init_resource::<RemoteScoring>()
+			// Only the server knows the answer, until the round is over.
+			.insert_resource(UnknownAnswer)
+			.add_system(send_guess.after(SysLabel::Input))
+			.add_system(receive)
+		;
+	}
+}
+
+/// A connection to a race server.
+#[derive(Resource)]
+pub struct Connection {
+	/// The id the server gave us.
+	pub id: u32,
+	/// The round being played. Guesses are sent with it, so that late ones don't count in the next round.
+	round: AtomicU32,
+	word_length: usize,
+	guesses: usize,
+	stream: TcpStream,
+	/// Messages read by the reader thread.
+	incoming: Mutex<Receiver<ServerMessage>>,
+}
+
+impl Connection {
+	/// Joins the race on a server. This blocks until the server replies, or gives up after [`CONNECT_TIMEOUT`].
+	/// The server turns us away if the race is played in another language.
+	pub fn connect(addr: &str, language: Language, name: &str) -> io::Result<Self> {
+		let mut stream = Err(io::Error::new(io::ErrorKind::NotFound, "the address did not resolve"));
+		for addr in addr.to_socket_addrs()? {
+			stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT);
+			if stream.is_ok() { break; }
+		}
+		let stream = stream?;
+		stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+		let mut lines = BufReader::new(stream.try_clone()?).lines();
+		
+		let mut writer = &stream;
+		let language = language.to_possible_value().unwrap().get_name().to_owned();
+		writeln!(writer, "{}", ClientMessage::Join { language, name: name.to_owned() })?;
+		
+		let welcome = match lines.next().transpose() {
+			Ok(line) => line.and_then(|line| ServerMessage::parse(&line)),
+			Err(e) if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) =>
+				return Err(io::Error::new(io::ErrorKind::TimedOut, "the server did not answer")),
+			Err(e) => return Err(e),
+		};
+		let (id, round, word_length, guesses) = match welcome {
+			Some(ServerMessage::Welcome { id, round, word_length, guesses }) => (id, round, word_length, guesses),
+			Some(ServerMessage::Rejected { reason: Rejection::Language(language) }) => return Err(io::Error::new(
+				io::ErrorKind::PermissionDenied,
+				format!("this race is played in {}", language),
+			)),
+			_ => return Err(io::Error::new(io::ErrorKind::InvalidData, "the server did not welcome us")),
+		};
+		// Once in, the race can go quiet for as long as nobody guesses.
+		stream.set_read_timeout(None)?;
+		
+		let (sender, receiver) = channel();
+		thread::spawn(move || {
+			for line in lines {
+				let message = match line {
+					Ok(line) => ServerMessage::parse(&line),
+					Err(_) => break,
+				};
+				if let Some(message) = message {
+					if sender.send(message).is_err() { break; }
+				}
+			}
+		});
+		
+		Ok(Connection {
+			id,
+			round: AtomicU32::new(round),
+			word_length,
+			guesses,
+			stream,
+			incoming: Mutex::new(receiver),
+		})
+	}
+	
+	/// Plays the game that the server picked, so that every player has the same board.
+	pub fn apply(&self, settings: &mut GameSettings) {
+		settings.mode = GameMode::Classic;
+		settings.word_length = self.word_length;
+		settings.guesses = self.guesses;
+	}
+	
+	/// Sends a message. Errors are ignored; the reader thread stops if the server is gone.
+	fn send(&self, message: &ClientMessage) {
+		let mut writer = &self.stream;
+		let _ = writeln!(writer, "{}", message);
+	}
+}
+
+/// The other players in the race.
+#[derive(Default)]
+#[derive(Resource)]
+pub struct Opponents(pub BTreeMap<u32, Opponent>);
+
+pub struct Opponent {
+	pub name: String,
+	/// The colors of each guess, without the letters.
+	pub rows: Vec<Vec<TileType>>,
+	/// Set when the player disconnects.
+	pub left: bool,
+}
+
+fn to_tile_type(mark: Mark) -> TileType {
+	match mark {
+		Mark::Correct => TileType::Correct,
+		Mark::Close => TileType::Close,
+		Mark::Wrong => TileType::Wrong,
+	}
+}
+
+/// Sends a submitted guess to the server, which scores it and shares the colors with every player.
+fn send_guess(
+	mut scoring: ResMut<RemoteScoring>,
+	connection: Res<Connection>,
+) {
+	if let Some(word) = scoring.outgoing.take() {
+		let round = connection.round.load(Ordering::Relaxed);
+		connection.send(&ClientMessage::Guess { round, word });
+	}
+}
+
+/// A round that starts once the end of the last one has been shown.
+struct NextRound {
+	round: u32,
+	timer: Timer,
+}
+
+/// Applies messages from the server.
+/// The result of the race is held back while a row is flipping, so it does not give away our own result.
+fn receive(
+	connection: Res<Connection>,
+	mut opponents: ResMut<Opponents>,
+	mut scoring: ResMut<RemoteScoring>,
+	mut held: Local<Vec<ServerMessage>>,
+	mut next_round: Local<Option<NextRound>>,
+	pause: Res<Pause>,
+	time: Res<Time>,
+	loc: Res<Localization>,
+	mut new_game_w: EventWriter<NewGame>,
+	mut toast_w: EventWriter<ShowToast>,
+) {
+	let incoming = connection.incoming.lock().unwrap();
+	let mut messages: Vec<_> = held.drain(..).collect();
+	messages.extend(incoming.try_iter());
+	
+	let name = |opponents: &Opponents, id: u32| opponents.0.get(&id)
+		.map(|opponent| opponent.name.clone())
+		.unwrap_or_default();
+	let answer_toast = |answer: &str| {
+		let answer: String = answer.chars().map(upper).collect();
+		ShowToast(loc.format("race-answer", &[("word", &answer)]))
+	};
+	
+	for message in messages {
+		match message {
+			ServerMessage::Joined { id, name } if id != connection.id => {
+				toast_w.send(ShowToast(loc.format("race-joined", &[("name", &name)])));
+				opponents.0.insert(id, Opponent { name, rows: Vec::new(), left: false });
+			}
+			// Our own guess comes back scored.
+			ServerMessage::Progress { id, marks } if id == connection.id => {
+				scoring.reply = Some(Ok(marks.into_iter().map(to_tile_type).collect()));
+			}
+			ServerMessage::Rejected { reason } => {
+				let reason = match reason {
+					Rejection::UnknownWord => InvalidReason::NotInWordList,
+					Rejection::RoundOver => InvalidReason::RoundOver,
+					// Only sent when joining.
+					Rejection::Language(_) => continue,
+				};
+				scoring.reply = Some(Err(reason));
+			}
+			// Guesses in the next round wait until it starts here too.
+			ServerMessage::Progress { .. } if next_round.is_some() => held.push(message),
+			ServerMessage::Progress { id, marks } if id != connection.id => {
+				if let Some(opponent) = opponents.0.get_mut(&id) {
+					opponent.rows.push(marks.into_iter().map(to_tile_type).collect());
+				}
+			}
+			ServerMessage::Winner { .. } | ServerMessage::Draw { .. } if pause.paused() => held.push(message),
+			ServerMessage::Winner { id, .. } if id == connection.id => {
+				toast_w.send(ShowToast(loc.get("race-you-win").to_owned()));
+			}
+			ServerMessage::Winner { id, answer } => {
+				toast_w.send(ShowToast(loc.format("race-win", &[("name", &name(&opponents, id))])));
+				toast_w.send(answer_toast(&answer));
+			}
+			ServerMessage::Draw { answer } => {
+				toast_w.send(ShowToast(loc.get("race-draw").to_owned()));
+				toast_w.send(answer_toast(&answer));
+			}
+			ServerMessage::Round { round } => {
+				*next_round = Some(NextRound { round, timer: Timer::new(NEXT_ROUND_DELAY, TimerMode::Once) });
+			}
+			ServerMessage::Left { id } => {
+				toast_w.send(ShowToast(loc.format("race-left", &[("name", &name(&opponents, id))])));
+				if let Some(opponent) = opponents.0.get_mut(&id) {
+					opponent.left = true;
+				}
+			}
+			_ => {}
+		}
+	}
+	
+	if pause.paused() { return; }
+	let started = match &mut *next_round {
+		Some(next) => next.timer.tick(time.delta()).finished(),
+		None => false,
+	};
+	if started {
+		let next = next_round.take().unwrap();
+		connection.round.store(next.round, Ordering::Relaxed);
+		for opponent in opponents.0.values_mut() {
+			opponent.rows.clear();
+		}
+		*scoring = RemoteScoring::default();
+		new_game_w.send(NewGame);
+		toast_w.send(ShowToast(loc.get("race-next-round").to_owned()));
+	}
+}
+
+#[test]
+fn test_score_matches_correctness() {
+	use crate::correctness;
+	use wordle::protocol::score;
+	
+	for (answer, guess) in [("hello", "lemon"), ("slips", "ssssk"), ("mints", "mmmmm"), ("señal", "señor")] {
+		let marks: Vec<_> = correctness(answer, guess).into_iter().map(|tt| match tt {
+			TileType::Correct => Mark::Correct,
+			TileType::Close => Mark::Close,
+			_ => Mark::Wrong,
+		}).collect();
+		assert_eq!(score(answer, guess), marks, "{} guessed as {}", answer, guess);
+	}
+}
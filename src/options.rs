@@ -0,0 +1,207 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use bevy::window::WindowMode;
+use clap::{CommandFactory, Parser};
+use clap::error::ErrorKind;
+use wordle::language::{Language, LanguagePack};
+use crate::components::{AnimSettings, AudioSettings, BotSettings, DisplaySettings, GameMode, GameSettings};
+use crate::keyboard::{KeyboardLayout, Layout};
+use crate::particles::ConfettiSettings;
+use crate::theme::Theme;
+
+/// Command-line options for the native binary.
+#[derive(Parser, Debug)]
+#[command(name = "wordle", about = "A Wordle clone made with Bevy.")]
+pub struct Options {
+	/// The number of letters in each word.
+	#[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u8).range(2..=10))]
+	pub word_length: u8,
+	/// The number of guesses the player gets.
+	#[arg(long, default_value_t = 6, value_parser = clap::value_parser!(u8).range(1..=10))]
+	pub guesses: u8,
+	/// Any revealed hints must be used in subsequent guesses.
+	#[arg(long)]
+	pub hard: bool,
+	/// Seed used to pick the answer.
+	#[arg(long, conflicts_with = "daily")]
+	pub seed: Option<u64>,
+	/// Play the word of the day.
+	#[arg(long)]
+	pub daily: bool,
+	/// How the game is played.
+	#[arg(long, value_enum, default_value_t = GameMode::Classic)]
+	pub mode: GameMode,
+	/// The word for the computer to guess in bot mode. Without it, you color in its guesses yourself.
+	#[arg(long)]
+	pub secret: Option<String>,
+	/// Race other players on a server, like `192.168.1.10:7878`. The server picks the word, and it is played in classic mode.
+	#[cfg(not(target_family = "wasm"))]
+	#[arg(long, value_name = "ADDRESS")]
+	pub server: Option<String>,
+	/// Your name in a race.
+	#[cfg(not(target_family = "wasm"))]
+	#[arg(long, default_value = "Player")]
+	pub name: String,
+	/// The language of the words and keyboard.
+	#[arg(long, value_enum, default_value_t = Language::En)]
+	pub language: Language,
+	/// The language of the UI. Defaults to the language of the words.
+	#[arg(long, value_enum)]
+	pub ui_language: Option<Language>,
+	/// The layout of the on-screen keyboard. Defaults to the usual layout for the language.
+	#[arg(long, value_enum)]
+	pub layout: Option<Layout>,
+	/// A word list to use instead of the built in one. One word per line.
+	#[arg(long, value_name = "PATH")]
+	pub dictionary: Option<PathBuf>,
+	/// The colour theme.
+	#[arg(long, value_enum, default_value_t = Theme::Dark)]
+	pub theme: Theme,
+	/// Mark correct and close tiles with shapes, so that colour is not needed.
+	#[arg(long)]
+	pub markers: bool,
+	/// Show how long the game has taken. Your best time is saved.
+	#[arg(long)]
+	pub timer: bool,
+	/// How fast animations play; 2 is twice as fast.
+	#[arg(long, default_value_t = 1.0, value_parser = parse_speed)]
+	pub anim_speed: f32,
+	/// Replace movement with instant colour changes.
+	#[arg(long)]
+	pub reduced_motion: bool,
+	/// The volume of the sound effects, from 0 to 1.
+	#[arg(long, default_value_t = 0.5, value_parser = parse_volume)]
+	pub volume: f32,
+	/// Start with the sound muted. Press Ctrl+M to toggle mute.
+	#[arg(long)]
+	pub mute: bool,
+	/// How many pieces of confetti to shoot from each side on a win.
+	#[arg(long, default_value_t = ConfettiSettings::default().count)]
+	pub confetti: u32,
+	/// Run in a window of a fixed size. Without this, or `--fullscreen`, the window can be resized.
+	#[arg(long, conflicts_with = "fullscreen")]
+	pub windowed: bool,
+	/// Run fullscreen.
+	#[arg(long)]
+	pub fullscreen: bool,
+}
+
+impl Options {
+	/// Parses the options from the command line.
+	///
+	/// There is no command line on the web, so the defaults are always used there.
+	pub fn from_env() -> Self {
+		#[cfg(target_family = "wasm")]
+		return Options::parse_from(["wordle"]);
+		#[cfg(not(target_family = "wasm"))]
+		return Options::parse();
+	}
+
+	/// Exits with an error like the ones for bad arguments.
+	/// This is for problems that are only found after parsing, like a dictionary with no words of the right length.
+	pub fn error(message: impl Display) -> ! {
+		Options::command().error(ErrorKind::ValueValidation, message).exit()
+	}
+	
+	/// The [`WindowMode`] that was asked for.
+	pub fn window_mode(&self) -> WindowMode {
+		if self.fullscreen { WindowMode::BorderlessFullscreen } else { WindowMode::Windowed }
+	}
+
+	/// Whether the window can be resized. A `--windowed` window keeps its size.
+	pub fn resizable(&self) -> bool {
+		!self.windowed
+	}
+
+	/// Builds the [`KeyboardLayout`] resource for the language.
+	pub fn keyboard_layout(&self, pack: &LanguagePack) -> KeyboardLayout {
+		let layout = match self.layout {
+			Some(layout) => KeyboardLayout::built_in(layout),
+			None => KeyboardLayout { rows: pack.keyboard.clone() },
+		};
+		layout.for_language(pack)
+	}
+	
+	/// Builds the [`GameSettings`] resource.
+	pub fn settings(&self) -> GameSettings {
+		GameSettings {
+			word_length: self.word_length as usize,
+			guesses: self.guesses as usize,
+			hard: self.hard,
+			seed: self.answer_seed(),
+			seeded: self.seed.is_some() || self.daily,
+			dictionary: self.dictionary.clone(),
+			mode: self.mode,
+		}
+	}
+
+	/// Builds the [`AnimSettings`] resource.
+	pub fn anim_settings(&self) -> AnimSettings {
+		AnimSettings {
+			speed: self.anim_speed,
+			reduced_motion: self.reduced_motion,
+		}
+	}
+	
+	/// Builds the [`AudioSettings`] resource.
+	pub fn audio_settings(&self) -> AudioSettings {
+		AudioSettings {
+			volume: self.volume,
+			muted: self.mute,
+		}
+	}
+	
+	/// Builds the [`DisplaySettings`] resource.
+	pub fn display_settings(&self) -> DisplaySettings {
+		DisplaySettings {
+			markers: self.markers,
+			timer: self.timer,
+		}
+	}
+	
+	/// Builds the [`BotSettings`] resource.
+	pub fn bot_settings(&self) -> BotSettings {
+		BotSettings {
+			secret: self.secret.as_ref().map(|s| s.to_lowercase()),
+		}
+	}
+	
+	/// Builds the [`ConfettiSettings`] resource.
+	pub fn confetti_settings(&self) -> ConfettiSettings {
+		ConfettiSettings {
+			count: self.confetti,
+			..Default::default()
+		}
+	}
+	
+	/// Gets the seed used to pick the answer.
+	/// The daily seed is the number of days since the unix epoch, so everyone gets the same word.
+	fn answer_seed(&self) -> u64 {
+		if self.daily {
+			let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+			return now.as_secs() / (60 * 60 * 24);
+		}
+		self.seed.unwrap_or_else(rand::random)
+	}
+}
+
+/// Parses an animation speed, which must be positive.
+fn parse_speed(s: &str) -> Result<f32, String> {
+	let speed: f32 = s.parse().map_err(|_| format!("{:?} is not a number", s))?;
+	if speed > 0.0 && speed.is_finite() {
+		Ok(speed)
+	} else {
+		Err("the speed must be more than 0".to_owned())
+	}
+}
+
+/// Parses a volume, which must be from 0 to 1.
+fn parse_volume(s: &str) -> Result<f32, String> {
+	let volume: f32 = s.parse().map_err(|_| format!("{:?} is not a number", s))?;
+	if (0.0..=1.0).contains(&volume) {
+		Ok(volume)
+	} else {
+		Err("the volume must be from 0 to 1".to_owned())
+	}
+}
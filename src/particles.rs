@@ -0,0 +1,220 @@
+use std::time::Duration;
+use bevy::prelude::*;
+use rand::{Rng, thread_rng};
+use statrs::distribution::{ContinuousCDF, Normal};
+use crate::{all_correct, SysLabel};
+use crate::components::AnimSettings;
+use crate::events::EndFlipAnim;
+use crate::layout::ScreenLayout;
+
+/// Downward acceleration, in units per second squared.
+const GRAVITY: f32 = 600.0;
+/// The fraction of velocity that is lost to air each second.
+const DRAG: f32 = 0.6;
+/// How long confetti lives.
+const CONFETTI_LIFETIME: Duration = Duration::from_secs(4);
+/// Confetti fades out for this long before it is despawned.
+const CONFETTI_FADE: Duration = Duration::from_secs(1);
+/// Confetti is drawn over the board.
+const CONFETTI_Z: f32 = 10.0;
+/// The height of the spawners, relative to the bottom of the view.
+const SPAWNER_HEIGHT: f32 = 340.0;
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.add_startup_system(setup_confetti_spawners)
+			.add_system(place_confetti_spawners)
+			.add_system_set(SystemSet::new()
+				.label(SysLabel::Anim)
+				.after(SysLabel::Input)
+				
+				.with_system(spawn_confetti)
+				.with_system(move_particles)
+				.with_system(expire_particles)
+			)
+		;
+	}
+}
+
+/// Settings for the confetti.
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct ConfettiSettings {
+	/// How many pieces each spawner shoots.
+	pub count: u32,
+	/// The colors to pick from. Random colors are used if this is empty.
+	pub colors: Vec<Color>,
+	/// The most particles that can be alive at once.
+	pub max_particles: usize,
+}
+
+impl Default for ConfettiSettings {
+	fn default() -> Self {
+		ConfettiSettings {
+			count: 200,
+			colors: Vec::new(),
+			// Web builds often run on phones.
+			max_particles: if cfg!(target_family = "wasm") { 250 } else { 2000 },
+		}
+	}
+}
+
+/// Shoots confetti when the game is won. It is kept at a side of the view.
+#[derive(Component)]
+pub struct ConfettiSpawner {
+	/// The mean velocity of the confetti, in units per second.
+	pub dir: Vec3,
+	/// The side of the view: -1.0 for left, 1.0 for right.
+	pub side: f32,
+}
+
+/// A particle that moves on its own until its lifetime is over.
+#[derive(Component)]
+pub struct Particle {
+	/// Velocity in units per second.
+	pub velocity: Vec3,
+	/// Spin in radians per second.
+	pub spin: f32,
+	/// How long the particle has left.
+	pub lifetime: Timer,
+}
+
+fn setup_confetti_spawners(
+	mut commands: Commands,
+	layout: Res<ScreenLayout>,
+) {
+	for side in [-1.0, 1.0] {
+		commands
+			.spawn(ConfettiSpawner {
+				dir: Vec3::new(-side * 300.0, 900.0, 0.0),
+				side,
+			})
+			.insert(Transform::from_translation(spawner_pos(side, &layout)));
+	}
+}
+
+/// Keeps the spawners at the edges of the view when the window is resized.
+fn place_confetti_spawners(
+	mut spawner_q: Query<(&mut Transform, &ConfettiSpawner)>,
+	layout: Res<ScreenLayout>,
+) {
+	if !layout.is_changed() { return; }
+	
+	for (mut transform, spawner) in spawner_q.iter_mut() {
+		transform.translation = spawner_pos(spawner.side, &layout);
+	}
+}
+
+/// Gets the position of a spawner on a side of the view.
+fn spawner_pos(side: f32, layout: &ScreenLayout) -> Vec3 {
+	Vec3::new(side * layout.view.x / 2.0, layout.bottom() + SPAWNER_HEIGHT, CONFETTI_Z)
+}
+
+fn spawn_confetti(
+	mut commands: Commands,
+	confetti_spawner_q: Query<(&Transform, &ConfettiSpawner)>,
+	particle_q: Query<(), With<Particle>>,
+	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+	anim_settings: Res<AnimSettings>,
+	confetti_settings: Res<ConfettiSettings>,
+) {
+	for end_flip_anim in end_flip_anim_r.iter() {
+		let end_flip_anim: &EndFlipAnim = end_flip_anim;
+		if anim_settings.reduced_motion || !all_correct(&end_flip_anim.correctness) { continue; }
+		
+		let mut rng = thread_rng();
+		// Never go over the cap.
+		let mut available = confetti_settings.max_particles.saturating_sub(particle_q.iter().count());
+		
+		for (transform, confetti_spawner) in confetti_spawner_q.iter() {
+			let transform: &Transform = transform;
+			let confetti_spawner: &ConfettiSpawner = confetti_spawner;
+			
+			let v_x = confetti_spawner.dir.x;
+			let v_y = confetti_spawner.dir.y;
+			
+			let normal_x = Normal::new(v_x as f64, (v_x.abs() / 2.0) as f64).unwrap();
+			let normal_y = Normal::new(v_y as f64, (v_y.abs() / 4.0) as f64).unwrap();
+			
+			let count = (confetti_settings.count as usize).min(available);
+			available -= count;
+			
+			for _i in 0..count {
+				let velocity = Vec3::new(
+					normal_x.inverse_cdf(rng.gen_range(0.0..1.0)) as f32,
+					normal_y.inverse_cdf(rng.gen_range(0.0..1.0)) as f32,
+					0.0,
+				);
+				
+				let color = if confetti_settings.colors.is_empty() {
+					Color::rgb_u8(rng.gen(), rng.gen(), rng.gen())
+				} else {
+					confetti_settings.colors[rng.gen_range(0..confetti_settings.colors.len())]
+				};
+				
+				// Stagger the lifetimes a little so the confetti does not all vanish at once.
+				let lifetime = CONFETTI_LIFETIME.mul_f32(rng.gen_range(0.8..1.2));
+				
+				commands
+					.spawn(SpriteBundle {
+						sprite: Sprite {
+							color,
+							custom_size: Some(Vec2::new(10.0, 5.0)),
+							..Default::default()
+						},
+						transform: *transform,
+						..Default::default()
+					})
+					.insert(Particle {
+						velocity,
+						spin: rng.gen_range(-12.0..12.0),
+						lifetime: Timer::new(lifetime, TimerMode::Once),
+					})
+				;
+			}
+		}
+	}
+}
+
+/// Moves particles using their velocity, gravity and drag.
+fn move_particles(
+	mut particle_q: Query<(&mut Transform, &mut Particle)>,
+	time: Res<Time>,
+) {
+	let dt = time.delta_seconds();
+	
+	for (transform, particle) in particle_q.iter_mut() {
+		let mut transform: Mut<Transform> = transform;
+		let mut particle: Mut<Particle> = particle;
+		
+		particle.velocity.y -= GRAVITY * dt;
+		particle.velocity *= (1.0 - DRAG).powf(dt);
+		
+		transform.translation += particle.velocity * dt;
+		transform.rotate_z(particle.spin * dt);
+	}
+}
+
+/// Fades particles out at the end of their lifetime, then despawns them.
+fn expire_particles(
+	mut commands: Commands,
+	mut particle_q: Query<(Entity, &mut Particle, &mut Sprite)>,
+	time: Res<Time>,
+) {
+	for (entity, particle, sprite) in particle_q.iter_mut() {
+		let entity: Entity = entity;
+		let mut particle: Mut<Particle> = particle;
+		let mut sprite: Mut<Sprite> = sprite;
+		
+		if particle.lifetime.tick(time.delta()).finished() {
+			commands.entity(entity).despawn();
+			continue;
+		}
+		
+		let remaining = particle.lifetime.remaining().as_secs_f32();
+		sprite.color.set_a((remaining / CONFETTI_FADE.as_secs_f32()).min(1.0));
+	}
+}
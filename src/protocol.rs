@@ -0,0 +1,286 @@
+//! The messages sent between the race server and its clients.
+//!
+//! Each message is a line of text: a command word followed by its fields, separated by spaces.
+//! The server picks the answer and scores every guess itself, so a client can't claim a win it didn't make.
+//! The answer is only sent once the round is over. Until then, a player learns the [`Mark`]s of their own guesses from
+//! the [`ServerMessage::Progress`] that the server sends everyone, and other players never see the letters of a guess.
+
+use std::fmt::{Display, Formatter};
+
+/// The correctness of one letter of a guess.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
+pub enum Mark {
+	Correct,
+	Close,
+	Wrong,
+}
+
+impl Mark {
+	fn to_char(self) -> char {
+		match self {
+			Mark::Correct => 'C',
+			Mark::Close => 'P',
+			Mark::Wrong => 'W',
+		}
+	}
+
+	fn from_char(c: char) -> Option<Self> {
+		match c {
+			'C' => Some(Mark::Correct),
+			'P' => Some(Mark::Close),
+			'W' => Some(Mark::Wrong),
+			_ => None,
+		}
+	}
+}
+
+/// Formats marks like `CPWWC`.
+fn marks_to_string(marks: &[Mark]) -> String {
+	marks.iter().map(|m| m.to_char()).collect()
+}
+
+/// Parses marks like `CPWWC`.
+fn parse_marks(s: &str) -> Option<Vec<Mark>> {
+	if s.is_empty() { return None; }
+	s.chars().map(Mark::from_char).collect()
+}
+
+/// Returns whether every letter of a guess is correct.
+pub fn solved(marks: &[Mark]) -> bool {
+	marks.iter().all(|m| *m == Mark::Correct)
+}
+
+/// Scores a guess against the answer, the same way the game colors it.
+/// Both words must have the same number of letters.
+pub fn score(answer: &str, guess: &str) -> Vec<Mark> {
+	let guess: Vec<_> = guess.chars().collect();
+	// Letters are set to `None` once matched.
+	let mut answer: Vec<_> = answer.chars().map(Some).collect();
+	let mut marks = vec![Mark::Wrong; guess.len()];
+	
+	for idx in 0..guess.len() {
+		if answer[idx] == Some(guess[idx]) {
+			marks[idx] = Mark::Correct;
+			answer[idx] = None;
+		}
+	}
+	for idx in 0..guess.len() {
+		if marks[idx] == Mark::Correct { continue; }
+		if let Some(found) = answer.iter().position(|c| *c == Some(guess[idx])) {
+			marks[idx] = Mark::Close;
+			answer[found] = None;
+		}
+	}
+	
+	marks
+}
+
+/// Why the server turned down a [`ClientMessage`].
+#[derive(Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
+pub enum Rejection {
+	/// The client plays in another language than the race, which is given. The server then disconnects.
+	Language(String),
+	/// The guess is not in the server's word list.
+	UnknownWord,
+	/// The guess is for a round that is over, or the player has no guesses left in it.
+	RoundOver,
+}
+
+impl Display for Rejection {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Rejection::Language(language) => write!(f, "language {}", language),
+			Rejection::UnknownWord => write!(f, "unknown-word"),
+			Rejection::RoundOver => write!(f, "round-over"),
+		}
+	}
+}
+
+impl Rejection {
+	/// Parses the fields of a `REJECTED` message.
+	fn parse(s: &str) -> Option<Self> {
+		match s.split_once(' ').unwrap_or((s, "")) {
+			("language", language) if !language.is_empty() => Some(Rejection::Language(language.to_owned())),
+			("unknown-word", "") => Some(Rejection::UnknownWord),
+			("round-over", "") => Some(Rejection::RoundOver),
+			_ => None,
+		}
+	}
+}
+
+/// A message from a client to the server.
+#[derive(Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
+pub enum ClientMessage {
+	/// The first message a client sends, with the language the client plays in.
+	Join { language: String, name: String },
+	/// The player guessed a word in a round of the race.
+	Guess { round: u32, word: String },
+}
+
+/// A message from the server to a client.
+#[derive(Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug)]
+pub enum ServerMessage {
+	/// The reply to [`ClientMessage::Join`], with the round that every player shares.
+	Welcome { id: u32, round: u32, word_length: usize, guesses: usize },
+	/// The reply to a [`ClientMessage`] that the server won't take.
+	Rejected { reason: Rejection },
+	/// The last round is over, and a new one starts with a new answer.
+	Round { round: u32 },
+	/// A player is in the game. Sent for each player that was already there, and for each new one.
+	Joined { id: u32, name: String },
+	/// A player made a guess.
+	Progress { id: u32, marks: Vec<Mark> },
+	/// A player solved the word first, which ends the round.
+	Winner { id: u32, answer: String },
+	/// Every player ran out of guesses, or the round ran out of time.
+	Draw { answer: String },
+	/// A player disconnected.
+	Left { id: u32 },
+}
+
+impl Display for ClientMessage {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ClientMessage::Join { language, name } => write!(f, "JOIN {} {}", language, name),
+			ClientMessage::Guess { round, word } => write!(f, "GUESS {} {}", round, word),
+		}
+	}
+}
+
+impl Display for ServerMessage {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ServerMessage::Welcome { id, round, word_length, guesses } =>
+				write!(f, "WELCOME {} {} {} {}", id, round, word_length, guesses),
+			ServerMessage::Rejected { reason } => write!(f, "REJECTED {}", reason),
+			ServerMessage::Round { round } => write!(f, "ROUND {}", round),
+			ServerMessage::Joined { id, name } => write!(f, "JOINED {} {}", id, name),
+			ServerMessage::Progress { id, marks } => write!(f, "PROGRESS {} {}", id, marks_to_string(marks)),
+			ServerMessage::Winner { id, answer } => write!(f, "WINNER {} {}", id, answer),
+			ServerMessage::Draw { answer } => write!(f, "DRAW {}", answer),
+			ServerMessage::Left { id } => write!(f, "LEFT {}", id),
+		}
+	}
+}
+
+impl ClientMessage {
+	/// Parses a line, or returns [`None`] if it is not a valid message.
+	pub fn parse(line: &str) -> Option<Self> {
+		let (command, rest) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+		let mut fields = rest.split(' ');
+
+		match command {
+			"JOIN" => {
+				let (language, name) = rest.split_once(' ').unwrap_or((rest, ""));
+				Some(ClientMessage::Join { language: language.to_owned(), name: sanitize_name(name) })
+			}
+			"GUESS" => Some(ClientMessage::Guess {
+				round: fields.next()?.parse().ok()?,
+				word: parse_word(fields.next()?)?,
+			}),
+			_ => None,
+		}
+	}
+}
+
+impl ServerMessage {
+	/// Parses a line, or returns [`None`] if it is not a valid message.
+	pub fn parse(line: &str) -> Option<Self> {
+		let (command, rest) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+		let mut fields = rest.split(' ');
+
+		match command {
+			"WELCOME" => Some(ServerMessage::Welcome {
+				id: fields.next()?.parse().ok()?,
+				round: fields.next()?.parse().ok()?,
+				word_length: fields.next()?.parse().ok()?,
+				guesses: fields.next()?.parse().ok()?,
+			}),
+			"REJECTED" => Some(ServerMessage::Rejected { reason: Rejection::parse(rest)? }),
+			"ROUND" => Some(ServerMessage::Round { round: fields.next()?.parse().ok()? }),
+			"JOINED" => {
+				let (id, name) = rest.split_once(' ')?;
+				Some(ServerMessage::Joined { id: id.parse().ok()?, name: name.to_owned() })
+			}
+			"PROGRESS" => Some(ServerMessage::Progress {
+				id: fields.next()?.parse().ok()?,
+				marks: parse_marks(fields.next()?)?,
+			}),
+			"WINNER" => Some(ServerMessage::Winner {
+				id: fields.next()?.parse().ok()?,
+				answer: parse_word(fields.next()?)?,
+			}),
+			"DRAW" => Some(ServerMessage::Draw { answer: parse_word(fields.next()?)? }),
+			"LEFT" => Some(ServerMessage::Left { id: fields.next()?.parse().ok()? }),
+			_ => None,
+		}
+	}
+}
+
+/// Parses a lowercase word, as the game stores them.
+fn parse_word(s: &str) -> Option<String> {
+	if s.is_empty() || !s.chars().all(|c| c.is_alphabetic() && !c.is_uppercase()) { return None; }
+	Some(s.to_owned())
+}
+
+/// Keeps names on one line, and not empty.
+fn sanitize_name(name: &str) -> String {
+	let name: String = name.chars().filter(|c| !c.is_control()).take(20).collect();
+	let name = name.trim();
+	if name.is_empty() { "Player".to_owned() } else { name.to_owned() }
+}
+
+#[test]
+fn test_protocol_round_trip() {
+	use Mark::*;
+
+	let client = [
+		ClientMessage::Join { language: "en".to_owned(), name: "Ada Lovelace".to_owned() },
+		ClientMessage::Guess { round: 2, word: "señal".to_owned() },
+	];
+	for message in client {
+		assert_eq!(ClientMessage::parse(&message.to_string()), Some(message));
+	}
+
+	let server = [
+		ServerMessage::Welcome { id: 3, round: 1, word_length: 5, guesses: 6 },
+		ServerMessage::Rejected { reason: Rejection::Language("es".to_owned()) },
+		ServerMessage::Rejected { reason: Rejection::UnknownWord },
+		ServerMessage::Rejected { reason: Rejection::RoundOver },
+		ServerMessage::Round { round: 2 },
+		ServerMessage::Joined { id: 1, name: "Ada Lovelace".to_owned() },
+		ServerMessage::Progress { id: 1, marks: vec![Close, Wrong] },
+		ServerMessage::Winner { id: 1, answer: "crane".to_owned() },
+		ServerMessage::Draw { answer: "adiós".to_owned() },
+		ServerMessage::Left { id: 2 },
+	];
+	for message in server {
+		assert_eq!(ServerMessage::parse(&message.to_string()), Some(message));
+	}
+
+	assert_eq!(ClientMessage::parse("GUESS 1 CRANE"), None);
+	assert_eq!(ClientMessage::parse("GUESS crane"), None);
+	assert_eq!(
+		ClientMessage::parse("JOIN en \u{7}"),
+		Some(ClientMessage::Join { language: "en".to_owned(), name: "Player".to_owned() }),
+	);
+	assert_eq!(ServerMessage::parse("WELCOME 1 2"), None);
+	assert_eq!(ServerMessage::parse("REJECTED because"), None);
+	assert_eq!(ServerMessage::parse("DRAW"), None);
+}
+
+#[test]
+fn test_score() {
+	use Mark::*;
+	assert_eq!(score("hello", "lemon"), [Close, Correct, Wrong, Close, Wrong]);
+	assert_eq!(score("slips", "ssssk"), [Correct, Close, Wrong, Wrong, Wrong]);
+	assert!(solved(&score("señal", "señal")));
+}
@@ -0,0 +1,103 @@
+//! Reverse mode: the answer is shown, along with the colors each row has to end up with.
+//!
+//! The player has to find words that give exactly those colors, working their way down to the answer.
+
+use bevy::prelude::*;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use wordle::util::upper;
+use crate::{correctness, get_tile_pos, TILE_SIZE, TileType};
+use crate::components::{GameSettings, Title, Word, WordDic};
+use crate::evil::hints;
+use crate::theme::Theme;
+
+const TARGET_TILE_SIZE: f32 = 20.0;
+const TARGET_TILE_MARGIN: f32 = 4.0;
+/// The space between the board and the targets.
+const TARGET_GAP: f32 = 20.0;
+
+pub struct ReversePlugin;
+
+impl Plugin for ReversePlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.add_system(show_targets)
+		;
+	}
+}
+
+/// The colors that each row has to match.
+#[derive(Clone, Debug)]
+#[derive(Resource)]
+pub struct Targets(pub Vec<Vec<TileType>>);
+
+impl Targets {
+	/// Picks the colors from random words, so that every row can be solved.
+	/// The rows give more away as they go down, and the last row is the answer.
+	pub fn new(answer: &str, dic: &WordDic, rows: usize, rng: &mut impl Rng) -> Self {
+		let others: Vec<_> = dic.iter().filter(|w| *w != answer).collect();
+		
+		let mut targets: Vec<_> = others.choose_multiple(rng, rows.saturating_sub(1))
+			.map(|w| correctness(answer, w))
+			.collect();
+		targets.sort_by_key(|t| hints(t));
+		targets.resize(rows, vec![TileType::Correct; answer.chars().count()]);
+		
+		Targets(targets)
+	}
+}
+
+/// Shows the answer in place of the title, and the colors to match beside each row.
+/// This waits for the targets to be added, as they are picked with the answer during setup.
+fn show_targets(
+	mut commands: Commands,
+	targets: Option<Res<Targets>>,
+	mut title_q: Query<&mut Text, With<Title>>,
+	word: Option<Res<Word>>,
+	settings: Res<GameSettings>,
+	theme: Res<Theme>,
+) {
+	let (targets, word) = match (targets, word) {
+		(Some(targets), Some(word)) if targets.is_added() => (targets, word),
+		_ => return,
+	};
+	
+	for mut text in title_q.iter_mut() {
+		text.sections[0].value = word.0.chars().map(upper).collect();
+	}
+	
+	for (row, target) in targets.0.iter().enumerate() {
+		let mut translation = get_tile_pos(settings.word_length - 1, row, &settings);
+		translation.x += (TILE_SIZE + TARGET_TILE_SIZE) / 2.0 + TARGET_GAP;
+		
+		for tt in target {
+			commands.spawn(SpriteBundle {
+				sprite: Sprite {
+					color: theme.tile_color(*tt),
+					custom_size: Some(Vec2::splat(TARGET_TILE_SIZE)),
+					..Default::default()
+				},
+				transform: Transform::from_translation(translation),
+				..Default::default()
+			});
+			translation.x += TARGET_TILE_SIZE + TARGET_TILE_MARGIN;
+		}
+	}
+}
+
+#[test]
+fn test_targets() {
+	use rand::SeedableRng;
+	use rand::rngs::StdRng;
+	
+	let dic = WordDic(["cat", "cot", "cut", "dog", "hat"].iter().map(|s| s.to_string()).collect());
+	let targets = Targets::new("cat", &dic, 4, &mut StdRng::seed_from_u64(0));
+	
+	assert_eq!(targets.0.len(), 4);
+	assert_eq!(targets.0[3], [TileType::Correct; 3]);
+	// Every row can be matched by a word that isn't the answer.
+	for target in &targets.0[..3] {
+		assert!(dic.iter().any(|w| w != "cat" && correctness("cat", w) == *target));
+	}
+	assert!(targets.0.windows(2).all(|w| hints(&w[0]) <= hints(&w[1])));
+}
@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use crate::{all_correct, SysLabel, TileType};
+use crate::anim::FlipLens;
+use crate::components::{AudioSettings, Tile};
+use crate::events::{EndFlipAnim, InvalidGuess, TypedLetter};
+use crate::tween::{Tween, tween_system};
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.add_startup_system(setup_sounds)
+			.add_system(toggle_mute)
+			.add_system_set(SystemSet::new()
+				.label(SysLabel::Anim)
+				.after(SysLabel::Input)
+				
+				.with_system(typed_letter_sound)
+				.with_system(invalid_guess_sound)
+				.with_system(flip_sound.after(tween_system::<FlipLens>))
+				.with_system(win_sound)
+			)
+		;
+	}
+}
+
+/// The sound effects.
+#[derive(Clone, Resource)]
+pub struct SoundAssets {
+	pub click: Handle<AudioSource>,
+	pub buzz: Handle<AudioSource>,
+	pub flip: Handle<AudioSource>,
+	pub win: Handle<AudioSource>,
+}
+
+fn setup_sounds(
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+) {
+	commands.insert_resource(SoundAssets {
+		click: asset_server.load("sounds/click.wav"),
+		buzz: asset_server.load("sounds/buzz.wav"),
+		flip: asset_server.load("sounds/flip.wav"),
+		win: asset_server.load("sounds/win.wav"),
+	});
+}
+
+/// Plays a sound at the volume in the [`AudioSettings`].
+/// The speed changes the pitch.
+fn play(audio: &Audio, audio_settings: &AudioSettings, sound: &Handle<AudioSource>, speed: f32) {
+	if audio_settings.muted || audio_settings.volume <= 0.0 { return; }
+	
+	audio.play_with_settings(
+		sound.clone(),
+		PlaybackSettings::ONCE
+			.with_volume(audio_settings.volume)
+			.with_speed(speed),
+	);
+}
+
+/// Toggles mute when Ctrl+M is pressed. M on its own types the letter.
+fn toggle_mute(
+	keys: Res<Input<KeyCode>>,
+	mut audio_settings: ResMut<AudioSettings>,
+) {
+	let ctrl = keys.any_pressed([KeyCode::LControl, KeyCode::RControl]);
+	if ctrl && keys.just_pressed(KeyCode::M) {
+		audio_settings.muted = !audio_settings.muted;
+	}
+}
+
+fn typed_letter_sound(
+	mut typed_letter_r: EventReader<TypedLetter>,
+	audio: Res<Audio>,
+	audio_settings: Res<AudioSettings>,
+	sounds: Res<SoundAssets>,
+) {
+	for typed_letter in typed_letter_r.iter() {
+		let typed_letter: &TypedLetter = typed_letter;
+		
+		if typed_letter.valid {
+			play(&audio, &audio_settings, &sounds.click, 1.0);
+		}
+	}
+}
+
+fn invalid_guess_sound(
+	mut inv_guess_r: EventReader<InvalidGuess>,
+	audio: Res<Audio>,
+	audio_settings: Res<AudioSettings>,
+	sounds: Res<SoundAssets>,
+) {
+	for _ in inv_guess_r.iter() {
+		play(&audio, &audio_settings, &sounds.buzz, 1.0);
+	}
+}
+
+/// Plays a sound as each tile flips over; the better the tile, the higher the pitch.
+fn flip_sound(
+	tiles: Query<(&Tile, &Tween<FlipLens>)>,
+	audio: Res<Audio>,
+	audio_settings: Res<AudioSettings>,
+	sounds: Res<SoundAssets>,
+) {
+	for (tile, tween) in tiles.iter() {
+		let tile: &Tile = tile;
+		let tween: &Tween<FlipLens> = tween;
+		
+		if tween.crossed(0.5) {
+			let speed = match tile.tt {
+				TileType::Correct => 1.25,
+				TileType::Close => 1.1,
+				_ => 1.0,
+			};
+			play(&audio, &audio_settings, &sounds.flip, speed);
+		}
+	}
+}
+
+/// Plays the jingle once the winning row is revealed.
+/// [`GameWin`](crate::events::GameWin) is fired as soon as the guess is checked, so waiting for the flip keeps the
+/// jingle from giving the result away early.
+fn win_sound(
+	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+	audio: Res<Audio>,
+	audio_settings: Res<AudioSettings>,
+	sounds: Res<SoundAssets>,
+) {
+	for end_flip_anim in end_flip_anim_r.iter() {
+		let end_flip_anim: &EndFlipAnim = end_flip_anim;
+		
+		if all_correct(&end_flip_anim.correctness) {
+			play(&audio, &audio_settings, &sounds.win, 1.0);
+		}
+	}
+}
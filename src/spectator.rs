@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+use crate::TileType;
+use crate::components::GameSettings;
+use crate::net::Opponents;
+use crate::theme::Theme;
+
+const MINI_TILE_SIZE: f32 = 12.0;
+const MINI_TILE_MARGIN: f32 = 1.0;
+const NAME_TEXT_SIZE: f32 = 18.0;
+
+/// A panel of mini boards that shows how the other players in a race are doing.
+/// Only the colors of their guesses are shown, so the answer is not given away.
+pub struct SpectatorPlugin;
+
+impl Plugin for SpectatorPlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.add_startup_system(setup_spectator_panel)
+			.add_system(update_spectator_panel)
+		;
+	}
+}
+
+/// The UI node that the mini boards are in.
+#[derive(Component)]
+struct SpectatorPanel;
+
+fn setup_spectator_panel(
+	mut commands: Commands,
+) {
+	commands
+		.spawn(NodeBundle {
+			background_color: Color::NONE.into(),
+			style: Style {
+				position_type: PositionType::Absolute,
+				position: UiRect {
+					top: Val::Px(20.0),
+					right: Val::Px(20.0),
+					..Default::default()
+				},
+				flex_direction: FlexDirection::Column,
+				align_items: AlignItems::FlexEnd,
+				..Default::default()
+			},
+			..Default::default()
+		})
+		.insert(SpectatorPanel)
+		.insert(Name::new("Spectator Panel"));
+}
+
+/// Rebuilds the mini boards whenever an opponent joins, guesses or leaves.
+fn update_spectator_panel(
+	mut commands: Commands,
+	panel_q: Query<Entity, With<SpectatorPanel>>,
+	opponents: Res<Opponents>,
+	settings: Res<GameSettings>,
+	theme: Res<Theme>,
+	asset_server: Res<AssetServer>,
+) {
+	if !opponents.is_changed() { return; }
+	
+	let font = asset_server.load("fonts/Swansea.ttf");
+	for panel in panel_q.iter() {
+		commands.entity(panel).despawn_descendants();
+		commands.entity(panel).with_children(|panel_cb| {
+			for opponent in opponents.0.values() {
+				// Players that left are faded out.
+				let name_color = if opponent.left { theme.tile_color(TileType::Default) } else { theme.text() };
+				
+				panel_cb
+					.spawn(NodeBundle {
+						background_color: Color::NONE.into(),
+						style: Style {
+							margin: UiRect::all(Val::Px(8.0)),
+							flex_direction: FlexDirection::Column,
+							align_items: AlignItems::Center,
+							..Default::default()
+						},
+						..Default::default()
+					})
+					.insert(Name::new(format!("{} board", opponent.name)))
+					.with_children(|board_cb| {
+						board_cb.spawn(TextBundle::from_section(
+							opponent.name.clone(),
+							TextStyle {
+								font: font.clone(),
+								font_size: NAME_TEXT_SIZE,
+								color: name_color,
+							},
+						));
+						
+						for row in 0..settings.guesses {
+							let row = opponent.rows.get(row);
+							spawn_mini_row(board_cb, row.map(|row| row.as_slice()), settings.word_length, *theme);
+						}
+					});
+			}
+		});
+	}
+}
+
+/// Spawns a row of a mini board. Rows that have not been guessed yet are empty.
+fn spawn_mini_row(board_cb: &mut ChildBuilder, row: Option<&[TileType]>, word_length: usize, theme: Theme) {
+	board_cb
+		.spawn(NodeBundle {
+			background_color: Color::NONE.into(),
+			style: Style {
+				flex_direction: FlexDirection::Row,
+				..Default::default()
+			},
+			..Default::default()
+		})
+		.with_children(|row_cb| {
+			for x in 0..word_length {
+				let tt = row.and_then(|row| row.get(x)).copied().unwrap_or(TileType::Default);
+				
+				row_cb.spawn(NodeBundle {
+					background_color: theme.tile_color(tt).into(),
+					style: Style {
+						margin: UiRect::all(Val::Px(MINI_TILE_MARGIN)),
+						size: Size::new(Val::Px(MINI_TILE_SIZE), Val::Px(MINI_TILE_SIZE)),
+						..Default::default()
+					},
+					..Default::default()
+				});
+			}
+		});
+}
@@ -0,0 +1,112 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+use bevy::prelude::*;
+
+/// The name of the stats file in the home directory, or the key in local storage on the web.
+const STATS_NAME: &str = ".wordle-stats";
+
+/// The player's records, which are kept between games.
+#[derive(Clone, Debug)]
+#[derive(Default, PartialEq)]
+#[derive(Resource)]
+pub struct Stats {
+	/// The fastest win in classic mode, with the usual rules.
+	pub best_time: Option<Duration>,
+	/// The most words solved in one time attack.
+	pub best_time_attack: u32,
+	/// The most words solved in a row in endless mode.
+	pub best_streak: u32,
+	/// How many guesses the best streak took.
+	pub best_streak_guesses: u32,
+}
+
+impl Stats {
+	/// Loads the saved stats. Missing or unreadable stats start over.
+	pub fn load() -> Self {
+		load_raw().map(|raw| Stats::parse(&raw)).unwrap_or_default()
+	}
+
+	/// Saves the stats. Errors are logged, as losing stats should not stop the game.
+	pub fn save(&self) {
+		if let Err(e) = save_raw(&self.to_string()) {
+			warn!("could not save the stats: {}", e);
+		}
+	}
+
+	/// Parses stats, which have a `key = value` pair on each line. Unknown keys are skipped.
+	fn parse(raw: &str) -> Self {
+		let mut stats = Stats::default();
+
+		for (key, value) in raw.lines().filter_map(|line| line.split_once('=')) {
+			let value = value.trim();
+			match key.trim() {
+				"best-time-ms" => stats.best_time = value.parse().ok().map(Duration::from_millis),
+				"best-time-attack" => stats.best_time_attack = value.parse().unwrap_or_default(),
+				"best-streak" => stats.best_streak = value.parse().unwrap_or_default(),
+				"best-streak-guesses" => stats.best_streak_guesses = value.parse().unwrap_or_default(),
+				_ => {}
+			}
+		}
+
+		stats
+	}
+}
+
+impl Display for Stats {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		if let Some(best_time) = self.best_time {
+			writeln!(f, "best-time-ms = {}", best_time.as_millis())?;
+		}
+		writeln!(f, "best-time-attack = {}", self.best_time_attack)?;
+		writeln!(f, "best-streak = {}", self.best_streak)?;
+		writeln!(f, "best-streak-guesses = {}", self.best_streak_guesses)
+	}
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn stats_path() -> std::path::PathBuf {
+	let home = std::env::var_os("HOME")
+		.or_else(|| std::env::var_os("USERPROFILE"))
+		.unwrap_or_default();
+	std::path::Path::new(&home).join(STATS_NAME)
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn load_raw() -> Option<String> {
+	std::fs::read_to_string(stats_path()).ok()
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn save_raw(raw: &str) -> Result<(), String> {
+	std::fs::write(stats_path(), raw).map_err(|e| e.to_string())
+}
+
+#[cfg(target_family = "wasm")]
+fn local_storage() -> Option<web_sys::Storage> {
+	web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_family = "wasm")]
+fn load_raw() -> Option<String> {
+	local_storage()?.get_item(STATS_NAME).ok()?
+}
+
+#[cfg(target_family = "wasm")]
+fn save_raw(raw: &str) -> Result<(), String> {
+	local_storage()
+		.ok_or_else(|| "local storage is not available".to_owned())?
+		.set_item(STATS_NAME, raw)
+		.map_err(|_| "local storage is full".to_owned())
+}
+
+#[test]
+fn test_stats_round_trip() {
+	let stats = Stats {
+		best_time: Some(Duration::from_millis(83_250)),
+		best_time_attack: 7,
+		best_streak: 12,
+		best_streak_guesses: 47,
+	};
+	assert_eq!(Stats::parse(&stats.to_string()), stats);
+	assert_eq!(Stats::parse("unknown = 1\nbest-time-attack = x"), Stats::default());
+}
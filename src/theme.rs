@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use clap::ValueEnum;
+use crate::{D_GREY, GREEN, L_GREY, TileType, YELLOW};
+
+/// The colour theme of the game.
+#[derive(Copy, Clone)]
+#[derive(Eq, PartialEq)]
+#[derive(Debug, Default)]
+#[derive(ValueEnum, Resource)]
+pub enum Theme {
+	#[default]
+	Dark,
+	Light,
+}
+
+impl Theme {
+	/// The clear colour of the window.
+	pub fn background(&self) -> Color {
+		match self {
+			Theme::Dark => Color::rgb_u8(0x12, 0x12, 0x13),
+			Theme::Light => Color::WHITE,
+		}
+	}
+
+	/// The colour of the title and of letters on uncoloured tiles.
+	pub fn text(&self) -> Color {
+		match self {
+			Theme::Dark => Color::WHITE,
+			Theme::Light => Color::rgb_u8(0x1a, 0x1a, 0x1b),
+		}
+	}
+
+	/// The tint of the outline on empty tiles.
+	pub fn outline(&self) -> Color {
+		match self {
+			Theme::Dark => Color::WHITE,
+			Theme::Light => Color::rgb_u8(0xd3, 0xd6, 0xda),
+		}
+	}
+
+	/// The colour of a tile or key with the given [`TileType`].
+	pub fn tile_color(&self, tt: TileType) -> Color {
+		match (self, tt) {
+			(Theme::Dark, TileType::Default) => *L_GREY,
+			(Theme::Dark, TileType::Correct) => *GREEN,
+			(Theme::Dark, TileType::Close) => *YELLOW,
+			(Theme::Dark, TileType::Wrong) => *D_GREY,
+			(Theme::Light, TileType::Default) => Color::rgb_u8(0xd3, 0xd6, 0xda),
+			(Theme::Light, TileType::Correct) => Color::rgb_u8(0x6a, 0xaa, 0x64),
+			(Theme::Light, TileType::Close) => Color::rgb_u8(0xc9, 0xb4, 0x58),
+			(Theme::Light, TileType::Wrong) => Color::rgb_u8(0x78, 0x7c, 0x7e),
+		}
+	}
+
+	/// The colour of the letter on a tile or key with the given [`TileType`].
+	pub fn letter_color(&self, tt: TileType) -> Color {
+		match tt {
+			TileType::Default => self.text(),
+			_ => Color::WHITE,
+		}
+	}
+}
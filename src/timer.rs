@@ -0,0 +1,242 @@
+use std::time::Duration;
+use bevy::prelude::*;
+use crate::{all_correct, SysLabel};
+use crate::components::{DisplaySettings, GameMode, GameOver, GameSettings};
+use crate::events::{EndFlipAnim, GameLoss, GameWin, NewGame, TypedLetter};
+use crate::locale::Localization;
+use crate::stats::Stats;
+use crate::toast::ShowToast;
+
+/// How long a time attack lasts.
+const TIME_ATTACK_LIMIT: Duration = Duration::from_secs(5 * 60);
+/// How long to show the end of a word in a time attack before the next one.
+const NEXT_WORD_DELAY: Duration = Duration::from_millis(1500);
+const TIMER_TEXT_SIZE: f32 = 28.0;
+
+/// The speedrun timer, and the time attack mode.
+pub struct TimerPlugin;
+
+impl Plugin for TimerPlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.init_resource::<GameTimer>()
+			.init_resource::<TimeAttack>()
+			.add_startup_system(setup_timer_text)
+			.add_system(start_timer.after(SysLabel::Input))
+			.add_system(stop_timer.after(SysLabel::Input))
+			.add_system(reset_timer.after(SysLabel::Input))
+			.add_system(record_best_time.after(stop_timer))
+			.add_system(tick_timer.after(start_timer).after(stop_timer).after(reset_timer))
+			.add_system(time_attack.after(tick_timer))
+			.add_system(update_timer_text.after(tick_timer))
+		;
+	}
+}
+
+/// How long the player has been playing.
+/// It starts on the first letter that is typed, and stops when the game ends.
+#[derive(Default)]
+#[derive(Resource)]
+pub struct GameTimer {
+	pub elapsed: Duration,
+	pub running: bool,
+}
+
+/// The progress of a time attack.
+#[derive(Default)]
+#[derive(Resource)]
+pub struct TimeAttack {
+	/// How many words have been solved.
+	pub solved: u32,
+	/// Counts down to the next word, after a word is won or lost.
+	next_word: Option<Timer>,
+	/// Set once the time has run out.
+	finished: bool,
+}
+
+/// The text that shows the time.
+#[derive(Component)]
+struct TimerText;
+
+fn setup_timer_text(
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+	settings: Res<GameSettings>,
+	display_settings: Res<DisplaySettings>,
+) {
+	if !display_settings.timer && settings.mode != GameMode::TimeAttack { return; }
+	
+	commands
+		.spawn(TextBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				position: UiRect {
+					top: Val::Px(20.0),
+					left: Val::Px(20.0),
+					..Default::default()
+				},
+				..Default::default()
+			},
+			text: Text::from_section(
+				"",
+				TextStyle {
+					font: asset_server.load("fonts/Swansea.ttf"),
+					font_size: TIMER_TEXT_SIZE,
+					color: Color::GRAY,
+				},
+			),
+			..Default::default()
+		})
+		.insert(TimerText)
+		.insert(Name::new("Timer"));
+}
+
+fn start_timer(
+	mut typed_letter_r: EventReader<TypedLetter>,
+	mut timer: ResMut<GameTimer>,
+	time_attack: Res<TimeAttack>,
+) {
+	if typed_letter_r.iter().count() > 0 && !timer.running && timer.elapsed.is_zero() && !time_attack.finished {
+		timer.running = true;
+	}
+}
+
+/// Stops the timer as soon as a classic game ends.
+/// A time attack, or an endless run, keeps the timer running between words.
+fn stop_timer(
+	mut game_win_r: EventReader<GameWin>,
+	mut game_loss_r: EventReader<GameLoss>,
+	mut timer: ResMut<GameTimer>,
+	settings: Res<GameSettings>,
+) {
+	let won = game_win_r.iter().count() > 0;
+	let lost = game_loss_r.iter().count() > 0;
+	// Endless mode runs until a word is missed.
+	let ended = lost || (won && settings.mode != GameMode::Endless);
+	if settings.mode == GameMode::TimeAttack || !ended { return; }
+	
+	timer.running = false;
+}
+
+/// Saves the time of a classic win if it is the best yet, once the winning row has been revealed.
+/// Only games with the usual rules count, see [`GameSettings::is_standard`].
+fn record_best_time(
+	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+	timer: Res<GameTimer>,
+	mut stats: ResMut<Stats>,
+	settings: Res<GameSettings>,
+	display_settings: Res<DisplaySettings>,
+	loc: Res<Localization>,
+	mut toast_w: EventWriter<ShowToast>,
+) {
+	let won = end_flip_anim_r.iter().any(|end_flip_anim| all_correct(&end_flip_anim.correctness));
+	if !won || !display_settings.timer || settings.mode != GameMode::Classic || !settings.is_standard() { return; }
+	
+	if stats.best_time.map_or(true, |best| timer.elapsed < best) {
+		stats.best_time = Some(timer.elapsed);
+		stats.save();
+		toast_w.send(ShowToast(loc.format("timer-best", &[("time", &format_time(timer.elapsed))])));
+	}
+}
+
+/// Starts the time over for each new game, except in the modes that time a whole run of words.
+fn reset_timer(
+	mut new_game_r: EventReader<NewGame>,
+	mut timer: ResMut<GameTimer>,
+	settings: Res<GameSettings>,
+) {
+	if new_game_r.iter().count() == 0 { return; }
+	if settings.mode == GameMode::TimeAttack || settings.mode == GameMode::Endless { return; }
+	
+	*timer = GameTimer::default();
+}
+
+fn tick_timer(
+	mut timer: ResMut<GameTimer>,
+	time: Res<Time>,
+) {
+	if timer.running {
+		timer.elapsed += time.delta();
+	}
+}
+
+/// Moves on to a new word after each word is revealed, until the time runs out.
+fn time_attack(
+	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+	mut time_attack: ResMut<TimeAttack>,
+	mut timer: ResMut<GameTimer>,
+	mut game_over: ResMut<GameOver>,
+	mut stats: ResMut<Stats>,
+	settings: Res<GameSettings>,
+	time: Res<Time>,
+	loc: Res<Localization>,
+	mut new_game_w: EventWriter<NewGame>,
+	mut toast_w: EventWriter<ShowToast>,
+) {
+	if settings.mode != GameMode::TimeAttack || time_attack.finished { return; }
+	
+	for end_flip_anim in end_flip_anim_r.iter() {
+		let won = all_correct(&end_flip_anim.correctness);
+		
+		if won {
+			time_attack.solved += 1;
+		}
+		if won || end_flip_anim.row + 1 == settings.guesses {
+			time_attack.next_word = Some(Timer::new(NEXT_WORD_DELAY, TimerMode::Once));
+		}
+	}
+	
+	if let Some(next_word) = &mut time_attack.next_word {
+		if next_word.tick(time.delta()).finished() {
+			time_attack.next_word = None;
+			new_game_w.send(NewGame);
+		}
+	}
+	
+	if timer.elapsed >= TIME_ATTACK_LIMIT {
+		timer.running = false;
+		game_over.0 = true;
+		time_attack.finished = true;
+		time_attack.next_word = None;
+		
+		let solved = time_attack.solved;
+		toast_w.send(ShowToast(loc.format("time-attack-over", &[("count", &solved)])));
+		if solved > stats.best_time_attack {
+			stats.best_time_attack = solved;
+			stats.save();
+			toast_w.send(ShowToast(loc.format("time-attack-best", &[("count", &solved)])));
+		}
+	}
+}
+
+fn update_timer_text(
+	mut text_q: Query<&mut Text, With<TimerText>>,
+	timer: Res<GameTimer>,
+	time_attack: Res<TimeAttack>,
+	settings: Res<GameSettings>,
+	loc: Res<Localization>,
+) {
+	if !timer.is_changed() && !time_attack.is_changed() { return; }
+	
+	for mut text in text_q.iter_mut() {
+		text.sections[0].value = if settings.mode == GameMode::TimeAttack {
+			let remaining = TIME_ATTACK_LIMIT.saturating_sub(timer.elapsed);
+			loc.format("time-attack-status", &[("time", &format_time(remaining)), ("count", &time_attack.solved)])
+		} else {
+			format_time(timer.elapsed)
+		};
+	}
+}
+
+/// Formats a time like `1:05.3`.
+fn format_time(time: Duration) -> String {
+	let tenths = time.as_millis() / 100;
+	format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}
+
+#[test]
+fn test_format_time() {
+	assert_eq!(format_time(Duration::ZERO), "0:00.0");
+	assert_eq!(format_time(Duration::from_millis(65_340)), "1:05.3");
+	assert_eq!(format_time(Duration::from_secs(5 * 60)), "5:00.0");
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+use bevy::prelude::*;
+use wordle::util::upper;
+use crate::SysLabel;
+use crate::events::{InvalidGuess, InvalidReason};
+use crate::locale::Localization;
+
+const TOAST_TIME: Duration = Duration::from_millis(1500);
+const TOAST_TEXT_SIZE: f32 = 24.0;
+
+pub struct ToastPlugin;
+
+impl Plugin for ToastPlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.add_event::<ShowToast>()
+			.add_startup_system(setup_toasts)
+			.add_system(invalid_guess_toast.after(SysLabel::Input))
+			.add_system(spawn_toast.after(invalid_guess_toast))
+			.add_system(expire_toasts)
+		;
+	}
+}
+
+/// An event that shows a short message at the top of the screen.
+pub struct ShowToast(pub String);
+
+/// A message that is removed once its timer finishes.
+#[derive(Component)]
+pub struct Toast {
+	timer: Timer,
+}
+
+/// The UI node that toasts are stacked in.
+#[derive(Component)]
+struct ToastContainer;
+
+fn setup_toasts(
+	mut commands: Commands,
+) {
+	commands
+		.spawn(NodeBundle {
+			background_color: Color::NONE.into(),
+			style: Style {
+				position_type: PositionType::Absolute,
+				position: UiRect {
+					top: Val::Px(100.0),
+					..Default::default()
+				},
+				size: Size::new(Val::Percent(100.0), Val::Auto),
+				flex_direction: FlexDirection::Column,
+				align_items: AlignItems::Center,
+				..Default::default()
+			},
+			..Default::default()
+		})
+		.insert(ToastContainer)
+		.insert(Name::new("Toasts"));
+}
+
+/// Shows why a guess was rejected.
+fn invalid_guess_toast(
+	mut inv_guess_r: EventReader<InvalidGuess>,
+	loc: Res<Localization>,
+	mut toast_w: EventWriter<ShowToast>,
+) {
+	for inv_guess in inv_guess_r.iter() {
+		let inv_guess: &InvalidGuess = inv_guess;
+		
+		let message = match inv_guess.reason {
+			InvalidReason::NotEnoughLetters => loc.get("invalid-not-enough-letters").to_owned(),
+			InvalidReason::NotInWordList => loc.get("invalid-not-in-word-list").to_owned(),
+			InvalidReason::MustBeAt(c, idx) =>
+				loc.format("invalid-must-be-at", &[("position", &(idx + 1)), ("letter", &upper(c))]),
+			InvalidReason::MustContain(c) =>
+				loc.format("invalid-must-contain", &[("letter", &upper(c))]),
+			InvalidReason::WrongPattern => loc.get("invalid-wrong-pattern").to_owned(),
+			InvalidReason::RoundOver => loc.get("invalid-round-over").to_owned(),
+		};
+		toast_w.send(ShowToast(message));
+	}
+}
+
+fn spawn_toast(
+	mut commands: Commands,
+	mut toast_r: EventReader<ShowToast>,
+	container_q: Query<Entity, With<ToastContainer>>,
+	asset_server: Res<AssetServer>,
+) {
+	for toast in toast_r.iter() {
+		let toast: &ShowToast = toast;
+		let font = asset_server.load("fonts/Swansea.ttf");
+		
+		let container = container_q.single();
+		commands.entity(container).with_children(|container_cb| {
+			container_cb
+				.spawn(NodeBundle {
+					background_color: Color::WHITE.into(),
+					style: Style {
+						margin: UiRect::all(Val::Px(4.0)),
+						padding: UiRect::all(Val::Px(12.0)),
+						..Default::default()
+					},
+					..Default::default()
+				})
+				.insert(Toast { timer: Timer::new(TOAST_TIME, TimerMode::Once) })
+				.with_children(|toast_cb| {
+					toast_cb.spawn(TextBundle::from_section(
+						toast.0.clone(),
+						TextStyle {
+							font: font.clone(),
+							font_size: TOAST_TEXT_SIZE,
+							color: Color::BLACK,
+						},
+					));
+				});
+		});
+	}
+}
+
+fn expire_toasts(
+	mut commands: Commands,
+	mut toast_q: Query<(Entity, &mut Toast)>,
+	time: Res<Time>,
+) {
+	for (entity, mut toast) in toast_q.iter_mut() {
+		if toast.timer.tick(time.delta()).just_finished() {
+			commands.entity(entity).despawn_recursive();
+		}
+	}
+}
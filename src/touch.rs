@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use crate::{SysLabel, TILE_SIZE};
+use crate::events::InputAction;
+use crate::layout::ScreenLayout;
+
+/// How far a touch has to move to the left to count as a swipe, in world units.
+const SWIPE_DISTANCE: f32 = TILE_SIZE;
+
+/// Touch gestures. Taps on the on-screen keyboard are handled by the keys themselves.
+pub struct TouchPlugin;
+
+impl Plugin for TouchPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_system(swipe_backspace.before(SysLabel::Input));
+		
+		#[cfg(target_family = "wasm")]
+		app.add_startup_system(disable_browser_gestures);
+	}
+}
+
+/// Removes a letter when the player swipes left over the board.
+/// Touches that start on the keyboard are left to the keys.
+fn swipe_backspace(
+	touches: Res<Touches>,
+	layout: Res<ScreenLayout>,
+	mut input_w: EventWriter<InputAction>,
+) {
+	// The top of the keyboard, in logical pixels from the bottom of the window.
+	let keyboard_top = layout.keyboard_height * layout.scale;
+	
+	for touch in touches.iter_just_released() {
+		if layout.touch_to_window(touch.start_position()).y < keyboard_top { continue; }
+		
+		let distance = touch.distance() / layout.scale;
+		if -distance.x >= SWIPE_DISTANCE && distance.y.abs() < distance.x.abs() / 2.0 {
+			input_w.send(InputAction::Backspace);
+		}
+	}
+}
+
+/// Stops the browser from zooming or scrolling the page when the canvas is pinched or double tapped.
+#[cfg(target_family = "wasm")]
+fn disable_browser_gestures() {
+	const VIEWPORT: &str = "width=device-width, initial-scale=1, maximum-scale=1, user-scalable=no";
+	const STYLE: &str = "html, body, canvas { touch-action: none; overscroll-behavior: none; }";
+	
+	let document = match web_sys::window().and_then(|window| window.document()) {
+		Some(document) => document,
+		None => return,
+	};
+	let head = match document.head() {
+		Some(head) => head,
+		None => return,
+	};
+	
+	if let Ok(meta) = document.create_element("meta") {
+		let _ = meta.set_attribute("name", "viewport");
+		let _ = meta.set_attribute("content", VIEWPORT);
+		let _ = head.append_child(&meta);
+	}
+	
+	if let Ok(style) = document.create_element("style") {
+		style.set_text_content(Some(STYLE));
+		let _ = head.append_child(&style);
+	}
+}
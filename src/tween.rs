@@ -0,0 +1,213 @@
+use std::f32::consts::PI;
+use std::marker::PhantomData;
+use std::time::Duration;
+use bevy::prelude::*;
+use crate::SysLabel;
+use crate::components::AnimSettings;
+
+/// An easing curve, which maps linear progress to eased progress.
+#[derive(Copy, Clone)]
+#[derive(Debug, Default)]
+#[derive(PartialEq)]
+pub enum Ease {
+	#[default]
+	Linear,
+	QuadIn,
+	QuadOut,
+	QuadInOut,
+	SineInOut,
+	/// Overshoots the end a little, then settles.
+	BackOut,
+}
+
+impl Ease {
+	/// Eases the progress `t`, which goes from 0 to 1.
+	pub fn apply(&self, t: f32) -> f32 {
+		match self {
+			Ease::Linear => t,
+			Ease::QuadIn => t * t,
+			Ease::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+			Ease::QuadInOut => if t < 0.5 { 2.0 * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(2) / 2.0 },
+			Ease::SineInOut => -((PI * t).cos() - 1.0) / 2.0,
+			Ease::BackOut => {
+				const C1: f32 = 1.70158;
+				const C3: f32 = C1 + 1.0;
+				1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+			}
+		}
+	}
+}
+
+/// Animates part of a component.
+pub trait Lens: Send + Sync + 'static {
+	/// The component that is animated.
+	type Target: Component;
+	
+	/// Sets the target for the eased progress `t`.
+	fn apply(&self, target: &mut Self::Target, t: f32);
+	
+	/// Sets the target once the tween is done.
+	fn finish(&self, target: &mut Self::Target) {
+		self.apply(target, 1.0);
+	}
+}
+
+/// Animates a component of the entity using a [`Lens`].
+/// The [`Tween`] removes itself once it is done, then fires a [`TweenDone`] event.
+#[derive(Component)]
+pub struct Tween<L: Lens> {
+	/// What is animated.
+	pub lens: L,
+	/// How long the animation takes, not counting the delay.
+	duration: Duration,
+	/// How long to wait before starting.
+	delay: Duration,
+	/// Elapsed duration, including the delay.
+	elapsed: Duration,
+	/// The progress before the last tick.
+	last_progress: f32,
+	ease: Ease,
+}
+
+impl<L: Lens> Tween<L> {
+	/// Constructs a new linear [`Tween`] that starts right away.
+	pub fn new(lens: L, duration: Duration) -> Self {
+		Tween {
+			lens,
+			duration,
+			delay: Duration::ZERO,
+			elapsed: Duration::ZERO,
+			last_progress: 0.0,
+			ease: Ease::Linear,
+		}
+	}
+	
+	/// Waits for `delay` before starting.
+	pub fn with_delay(mut self, delay: Duration) -> Self {
+		self.delay = delay;
+		self
+	}
+	
+	/// Uses an easing curve.
+	pub fn with_ease(mut self, ease: Ease) -> Self {
+		self.ease = ease;
+		self
+	}
+	
+	/// Ticks by duration, then returns whether it finished.
+	pub fn tick(&mut self, dur: Duration) -> bool {
+		self.last_progress = self.progress();
+		self.elapsed += dur;
+		self.elapsed >= self.delay + self.duration
+	}
+	
+	/// Returns whether the delay is over.
+	pub fn started(&self) -> bool {
+		self.elapsed > self.delay
+	}
+	
+	/// Returns a float of how completed the animation is, before easing.
+	pub fn progress(&self) -> f32 {
+		if !self.started() { return 0.0; }
+		if self.duration.is_zero() { return 1.0; }
+		
+		let elapsed = (self.elapsed - self.delay).as_secs_f32();
+		(elapsed / self.duration.as_secs_f32()).min(1.0)
+	}
+	
+	/// Returns whether the progress passed `progress` during the last tick.
+	pub fn crossed(&self, progress: f32) -> bool {
+		self.last_progress < progress && self.progress() >= progress
+	}
+}
+
+/// Starts a tween on each entity, each one starting `step` after the one before.
+/// `tween` makes the tween for the entity at an index, before the delay is added.
+pub fn stagger<L: Lens>(
+	commands: &mut Commands,
+	entities: impl IntoIterator<Item = Entity>,
+	step: Duration,
+	mut tween: impl FnMut(usize) -> Tween<L>,
+) {
+	for (idx, entity) in entities.into_iter().enumerate() {
+		let delay = step * idx as u32;
+		commands.entity(entity).insert(tween(idx).with_delay(delay));
+	}
+}
+
+/// An event that is fired when a [`Tween`] finishes.
+pub struct TweenDone<L: Lens> {
+	/// The entity that was animated.
+	pub entity: Entity,
+	_lens: PhantomData<L>,
+}
+
+/// Ticks all [`Tween`]s of a [`Lens`] and applies them.
+/// Time is scaled by [`AnimSettings::speed`].
+pub fn tween_system<L: Lens>(
+	mut commands: Commands,
+	mut tweens: Query<(Entity, &mut Tween<L>, &mut L::Target)>,
+	time: Res<Time>,
+	anim_settings: Res<AnimSettings>,
+	mut tween_done_w: EventWriter<TweenDone<L>>,
+) {
+	let delta = time.delta().mul_f32(anim_settings.speed);
+	
+	for (entity, mut tween, mut target) in tweens.iter_mut() {
+		if tween.tick(delta) { // Finished
+			tween.lens.finish(&mut target);
+			commands.entity(entity).remove::<Tween<L>>();
+			tween_done_w.send(TweenDone { entity, _lens: PhantomData });
+			continue;
+		}
+		
+		if tween.started() {
+			let t = tween.ease.apply(tween.progress());
+			tween.lens.apply(&mut target, t);
+		}
+	}
+}
+
+/// Adds tweens to an [`App`].
+pub trait TweenAppExt {
+	/// Adds the system and event for the tweens of a [`Lens`].
+	fn add_tween<L: Lens>(&mut self) -> &mut Self;
+}
+
+impl TweenAppExt for App {
+	fn add_tween<L: Lens>(&mut self) -> &mut Self {
+		self
+			.add_event::<TweenDone<L>>()
+			.add_system(tween_system::<L>
+				.label(SysLabel::Anim)
+				.after(SysLabel::Input)
+			)
+	}
+}
+
+#[test]
+fn test_tween_progress() {
+	struct Noop;
+	impl Lens for Noop {
+		type Target = Transform;
+		fn apply(&self, _target: &mut Transform, _t: f32) {}
+	}
+	
+	let mut tween = Tween::new(Noop, Duration::from_millis(100)).with_delay(Duration::from_millis(100));
+	assert!(!tween.tick(Duration::from_millis(50)));
+	assert_eq!(tween.progress(), 0.0);
+	assert!(!tween.tick(Duration::from_millis(100)));
+	assert!(tween.crossed(0.5));
+	assert!(!tween.tick(Duration::from_millis(10)));
+	assert!(!tween.crossed(0.5));
+	assert!(tween.tick(Duration::from_millis(100)));
+	assert_eq!(tween.progress(), 1.0);
+}
+
+#[test]
+fn test_ease_ends() {
+	for ease in [Ease::Linear, Ease::QuadIn, Ease::QuadOut, Ease::QuadInOut, Ease::SineInOut, Ease::BackOut] {
+		assert!(ease.apply(0.0).abs() < 1e-5, "{:?}", ease);
+		assert!((ease.apply(1.0) - 1.0).abs() < 1e-5, "{:?}", ease);
+	}
+}
@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+use crate::{all_correct, get_tile_pos, SysLabel, TEXT_SIZE, TILE_TOTAL};
+use crate::components::GameSettings;
+use crate::events::{EndFlipAnim, Guess};
+use crate::locale::Localization;
+use crate::theme::Theme;
+use crate::toast::ShowToast;
+
+const PLAYERS: usize = 2;
+const RESULT_TILE_SIZE: f32 = 20.0;
+const RESULT_TEXT_SIZE: f32 = 28.0;
+
+/// Two players take turns guessing rows of the same board. Whoever solves it first wins.
+pub struct VersusPlugin;
+
+impl Plugin for VersusPlugin {
+	fn build(&self, app: &mut App) {
+		app
+			.init_resource::<Histories>()
+			.add_startup_system(setup_versus.after(SysLabel::Setup))
+			.add_system_set(SystemSet::new()
+				.label(SysLabel::Anim)
+				.after(SysLabel::Input)
+				
+				.with_system(record_guesses)
+				.with_system(next_turn)
+				.with_system(show_result.after(record_guesses))
+			)
+		;
+	}
+}
+
+/// The guesses of each player.
+#[derive(Default)]
+#[derive(Resource)]
+pub struct Histories([Vec<Guess>; PLAYERS]);
+
+/// Gets the index of the player that guesses on a row.
+pub fn player_for_row(row: usize) -> usize {
+	row % PLAYERS
+}
+
+/// Labels each row with the player that guesses on it.
+fn setup_versus(
+	mut commands: Commands,
+	asset_server: Res<AssetServer>,
+	settings: Res<GameSettings>,
+	loc: Res<Localization>,
+	theme: Res<Theme>,
+) {
+	let style = TextStyle {
+		font: asset_server.load("fonts/Swansea.ttf"),
+		font_size: TEXT_SIZE,
+		color: theme.text(),
+	};
+	let alignment = TextAlignment {
+		vertical: VerticalAlign::Center,
+		horizontal: HorizontalAlign::Center,
+	};
+	
+	for row in 0..settings.guesses {
+		let mut translation = get_tile_pos(0, row, &settings);
+		translation.x -= TILE_TOTAL;
+		
+		let label = loc.format("versus-row-label", &[("player", &(player_for_row(row) + 1))]);
+		commands.spawn(Text2dBundle {
+			text: Text::from_section(label, style.clone()).with_alignment(alignment),
+			transform: Transform::from_translation(translation),
+			..Default::default()
+		});
+	}
+}
+
+fn record_guesses(
+	mut guess_r: EventReader<Guess>,
+	mut histories: ResMut<Histories>,
+) {
+	for guess in guess_r.iter() {
+		let guess: &Guess = guess;
+		histories.0[player_for_row(guess.row)].push(guess.clone());
+	}
+}
+
+/// Tells the players whose turn it is once a row is revealed.
+fn next_turn(
+	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+	settings: Res<GameSettings>,
+	loc: Res<Localization>,
+	mut toast_w: EventWriter<ShowToast>,
+) {
+	for end_flip_anim in end_flip_anim_r.iter() {
+		let end_flip_anim: &EndFlipAnim = end_flip_anim;
+		let next_row = end_flip_anim.row + 1;
+		if all_correct(&end_flip_anim.correctness) || next_row >= settings.guesses { continue; }
+		
+		let player = player_for_row(next_row) + 1;
+		toast_w.send(ShowToast(loc.format("versus-turn", &[("player", &player)])));
+	}
+}
+
+/// Shows who won, with the colors of every guess each player made.
+fn show_result(
+	mut commands: Commands,
+	mut end_flip_anim_r: EventReader<EndFlipAnim>,
+	histories: Res<Histories>,
+	settings: Res<GameSettings>,
+	loc: Res<Localization>,
+	theme: Res<Theme>,
+	asset_server: Res<AssetServer>,
+) {
+	for end_flip_anim in end_flip_anim_r.iter() {
+		let end_flip_anim: &EndFlipAnim = end_flip_anim;
+		
+		let message = if all_correct(&end_flip_anim.correctness) {
+			let winner = player_for_row(end_flip_anim.row) + 1;
+			loc.format("versus-win", &[("player", &winner)])
+		} else if end_flip_anim.row + 1 == settings.guesses {
+			loc.get("versus-draw").to_owned()
+		} else {
+			continue;
+		};
+		
+		let text_style = TextStyle {
+			font: asset_server.load("fonts/Swansea.ttf"),
+			font_size: RESULT_TEXT_SIZE,
+			color: theme.text(),
+		};
+		
+		commands
+			.spawn(NodeBundle {
+				background_color: Color::NONE.into(),
+				style: Style {
+					position_type: PositionType::Absolute,
+					size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+					justify_content: JustifyContent::Center,
+					align_items: AlignItems::Center,
+					..Default::default()
+				},
+				..Default::default()
+			})
+			.insert(Name::new("Versus Result"))
+			.with_children(|root_cb| {
+				root_cb
+					.spawn(NodeBundle {
+						background_color: theme.background().into(),
+						style: Style {
+							padding: UiRect::all(Val::Px(20.0)),
+							flex_direction: FlexDirection::Column,
+							align_items: AlignItems::Center,
+							..Default::default()
+						},
+						..Default::default()
+					})
+					.with_children(|panel_cb| {
+						panel_cb.spawn(TextBundle::from_section(message, text_style.clone()));
+						
+						panel_cb
+							.spawn(NodeBundle {
+								background_color: Color::NONE.into(),
+								style: Style {
+									flex_direction: FlexDirection::Row,
+									align_items: AlignItems::FlexStart,
+									..Default::default()
+								},
+								..Default::default()
+							})
+							.with_children(|players_cb| {
+								for (idx, history) in histories.0.iter().enumerate() {
+									spawn_history(players_cb, idx, history, &loc, &text_style, *theme);
+								}
+							});
+					});
+			});
+	}
+}
+
+/// Spawns a column with a player's name, and a row of colored squares for each of their guesses.
+fn spawn_history(
+	players_cb: &mut ChildBuilder,
+	player: usize,
+	history: &[Guess],
+	loc: &Localization,
+	text_style: &TextStyle,
+	theme: Theme,
+) {
+	let name = loc.format("versus-player", &[("player", &(player + 1))]);
+	
+	players_cb
+		.spawn(NodeBundle {
+			background_color: Color::NONE.into(),
+			style: Style {
+				margin: UiRect::all(Val::Px(16.0)),
+				flex_direction: FlexDirection::Column,
+				align_items: AlignItems::Center,
+				..Default::default()
+			},
+			..Default::default()
+		})
+		.with_children(|column_cb| {
+			column_cb.spawn(TextBundle::from_section(name, text_style.clone()));
+			
+			for guess in history {
+				column_cb
+					.spawn(NodeBundle {
+						background_color: Color::NONE.into(),
+						style: Style {
+							flex_direction: FlexDirection::Row,
+							..Default::default()
+						},
+						..Default::default()
+					})
+					.with_children(|row_cb| {
+						for tt in guess.correctness.iter() {
+							row_cb.spawn(NodeBundle {
+								background_color: theme.tile_color(*tt).into(),
+								style: Style {
+									margin: UiRect::all(Val::Px(2.0)),
+									size: Size::new(Val::Px(RESULT_TILE_SIZE), Val::Px(RESULT_TILE_SIZE)),
+									..Default::default()
+								},
+								..Default::default()
+							});
+						}
+					});
+			}
+		});
+}